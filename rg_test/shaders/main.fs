@@ -9,5 +9,5 @@ out vec4 Target;
 
 void main()
 {
-	 Target = vec4(a_Color.rgb, texture2D(Texture, a_Uv).r * a_Color.a);
+	 Target = a_Color * texture2D(Texture, a_Uv);
 }
\ No newline at end of file
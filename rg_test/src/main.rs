@@ -1,6 +1,6 @@
 use sdl2::{
     event::{WindowEvent, Event},
-    keyboard::Keycode,
+    keyboard::{Keycode, Mod},
     mouse::MouseButton,
     video::{
         GLProfile,
@@ -11,7 +11,6 @@ use sdl2::{
 
 use rg::TextureHandle;
 use rg::float2;
-use rg::Rect;
 
 use std::slice;
 use std::ptr;
@@ -460,6 +459,7 @@ impl rg::Renderer for RgOpenGlRenderer {
             gl::Disable(gl::DEPTH_TEST);
             gl::Enable(gl::BLEND);
             gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            gl::Enable(gl::SCISSOR_TEST);
             gl::Viewport(0, 0, self.width as _, self.height as _);
         }
         
@@ -507,7 +507,15 @@ impl rg::Renderer for RgOpenGlRenderer {
         
         for layer in list.commands() {
             for command in layer {
+                let clip = command.clip_rect;
+                let x = clip.0.max(0f32);
+                let y = clip.1.max(0f32);
+                let w = (clip.2.min(self.width) - x).max(0f32);
+                let h = (clip.3.min(self.height) - y).max(0f32);
+
                 unsafe {
+                    gl::Scissor(x as i32, (self.height - y - h) as i32, w as i32, h as i32);
+
                     if command.texture_id == 0 as _ {
                         gl::BindTexture(gl::TEXTURE_2D, self.texture.handle as _);
                     } else {
@@ -535,6 +543,13 @@ impl rg::Renderer for RgOpenGlRenderer {
 
         let handle = texture.handle;
 
+        // the shader multiplies by the texture color, single channel
+        // textures only carry alpha
+        unsafe {
+            let swizzle = [gl::ONE as i32, gl::ONE as i32, gl::ONE as i32, gl::RED as i32];
+            gl::TexParameteriv(gl::TEXTURE_2D, gl::TEXTURE_SWIZZLE_RGBA, swizzle.as_ptr());
+        }
+
         ::std::mem::forget(texture);
 
         (handle as TextureHandle, handle as TextureHandle)
@@ -574,6 +589,47 @@ impl rg::Renderer for RgOpenGlRenderer {
             );
         }*/
     }
+
+    fn create_texture_rgba8(&mut self, width: u32, height: u32, data: &[u8]) -> TextureHandle {
+        let texture = Texture::with_data_2d(
+            data,
+            width,
+            height,
+            gl::RGBA8,
+            gl::RGBA,
+            gl::LINEAR
+        );
+
+        let handle = texture.handle;
+
+        ::std::mem::forget(texture);
+
+        handle as TextureHandle
+    }
+
+    fn update_texture(&mut self, handle: TextureHandle, x: u32, y: u32, width: u32, height: u32, data: &[u8], stride: u32) {
+        let handle = handle as u32;
+
+        unsafe {
+            gl::PixelStorei(gl::UNPACK_ROW_LENGTH, stride as i32);
+            gl::BindTexture(gl::TEXTURE_2D, handle);
+            gl::TexSubImage2D(
+                gl::TEXTURE_2D,
+                0,
+                x as i32, y as i32,
+                width as i32, height as i32,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                data.as_ptr() as *const _
+            );
+        }
+    }
+
+    fn destroy_texture(&mut self, handle: TextureHandle) {
+        drop(Texture {
+            handle: handle as u32,
+        });
+    }
 }
 
 const WIDTH: i32 = 800;
@@ -618,6 +674,13 @@ fn handle_event(io: &mut rg::IoState, renderer: &mut rg::Renderer, event: &Event
                 io.mouse_down[idx] = false;
                 io.mouse_released[idx] = true;
             },
+            Event::MouseWheel {
+                x,
+                y,
+                ..
+            } => {
+                io.mouse_scroll += float2(*x as f32, *y as f32);
+            },
             Event::Window { win_event, .. } => {
                 match win_event {
                     WindowEvent::Resized(w, h) => {
@@ -629,6 +692,10 @@ fn handle_event(io: &mut rg::IoState, renderer: &mut rg::Renderer, event: &Event
                     _ => {}
                 }
             },
+            Event::KeyDown { keymod, .. } | Event::KeyUp { keymod, .. } => {
+                io.key_ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+                io.key_shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+            },
             /*Event::KeyDown {
                 keycode,
                 scancode,
@@ -820,6 +887,11 @@ fn main() {
     let mut renderer = RgOpenGlRenderer::new().unwrap();
     let mut cxt = rg::Context::new(Box::new(renderer));
 
+    let checker: Vec<u8> = (0..16 * 16)
+        .flat_map(|i| if (i % 16 / 4 + i / 16 / 4) % 2 == 0 { vec![0xff, 0x80, 0x20, 0xff] } else { vec![0x30, 0x30, 0x30, 0xff] })
+        .collect();
+    let checker = rg::Image::new(cxt.renderer.create_texture_rgba8(16, 16, &checker), [16, 16]);
+
 
     use rg::Renderer;
 
@@ -831,6 +903,15 @@ fn main() {
     let mut running = true;
 
     let mut text = String::from("Here is some sample text for text field");
+    let mut search = String::new();
+    let mut split_ratio = 0.35f32;
+    let mut another_tab_open = true;
+    let mut accent = rg::make_color(230, 179, 0, 255);
+    let log: Vec<String> = (0..20000).map(|i| format!("[{:05}] log entry", i)).collect();
+    let mut log_selection = rg::ListSelection::new(rg::SelectionMode::Multiple);
+    let mut assets: Vec<(String, u32)> = (0..200)
+        .map(|i| (format!("asset_{:03}.png", (i * 37) % 200), (i * 7919) % 4096))
+        .collect();
 
     let mut deltas = [0f32; 60];
     let mut frame = 0;
@@ -888,9 +969,9 @@ fn main() {
         }*/
             let height = cxt.available_height();
             cxt.row(rg::RowType::dynamic_ex(2, height));
-            cxt.column(Some(0.35f32));
+            cxt.column(Some(split_ratio));
             if cxt.begin_panel("Left", rg::PanelFlags::None) {
-                if cxt.begin_tab_bar("tabbar") {
+                if cxt.begin_tab_bar_ex("tabbar", rg::TabBarFlags::Reorderable | rg::TabBarFlags::FittingPolicyScroll) {
                     if cxt.begin_tab_item("Test Tab") {
                         cxt.row(rg::RowType::dynamic(1));
                         cxt.column(Some(1f32));
@@ -919,12 +1000,89 @@ fn main() {
                         cxt.row(rg::RowType::dynamic(1));
                         cxt.column(Some(1f32));
                         cxt.paragraph("Inside Tab 2!");
+
+                        cxt.row(rg::RowType::dynamic_ratios(&[0.25f32, -1f32, -1f32]));
+                        cxt.button_text("quarter");
+                        cxt.button_text("rest");
+                        cxt.button_text("rest");
+
+                        cxt.layout_space_begin(rg::LayoutFormat::Dynamic, 80f32, 2);
+                        cxt.layout_space_push(rg::Rect::new(float2(0f32, 0f32), float2(0.5f32, 0.5f32)));
+                        cxt.button_text("top left");
+                        cxt.layout_space_push(rg::Rect::new(float2(0.5f32, 0.5f32), float2(1f32, 1f32)));
+                        cxt.button_text("bottom right");
+                        cxt.layout_space_end();
+
+                        cxt.row_template_begin(24f32);
+                        cxt.row_template_push_static(120f32);
+                        cxt.row_template_push_dynamic();
+                        cxt.row_template_push_static(30f32);
+                        cxt.row_template_end();
+                        cxt.paragraph("Search");
+                        cxt.textfield("search", &mut search);
+                        cxt.button_text("x");
+
+                        cxt.row(rg::RowType::dynamic(1));
+                        cxt.column(Some(1f32));
+                        cxt.begin_hbox("toolbar", 4f32, rg::FlexAlign::Center);
+                        cxt.flex_item(rg::FlexItem::fixed(60f32));
+                        cxt.button_text("Open");
+                        cxt.flex_item(rg::FlexItem::flex(1f32).min(80f32));
+                        cxt.textfield("path", &mut search);
+                        cxt.flex_item(rg::FlexItem::fixed(30f32));
+                        cxt.spinner(8f32);
+                        cxt.end_hbox();
+
+                        cxt.row(rg::RowType::dynamic(1));
+                        cxt.column(Some(1f32));
+                        let cols = [rg::GridTrack::Auto, rg::GridTrack::Fraction(1f32), rg::GridTrack::Fraction(1f32)];
+                        let rows = [rg::GridTrack::Auto, rg::GridTrack::Auto];
+                        cxt.begin_grid("form", &cols, &rows);
+                        cxt.grid_cell(0, 0, 1, 2);
+                        cxt.button_text("Apply");
+                        cxt.grid_cell(1, 0, 1, 1);
+                        cxt.paragraph("Name");
+                        cxt.grid_cell(2, 0, 1, 1);
+                        cxt.textfield("name", &mut search);
+                        cxt.grid_cell(1, 1, 2, 1);
+                        cxt.spinner(8f32);
+                        cxt.end_grid();
                         cxt.end_tab_item();
                     }
-                    if cxt.begin_tab_item("Another Tab") {
+                    if cxt.begin_tab_item_open("Another Tab", &mut another_tab_open) {
                         cxt.row(rg::RowType::dynamic(1));
                         cxt.column(Some(1f32));
                         cxt.paragraph("Inside Tab 3!");
+
+                        cxt.row(rg::RowType::dynamic_ex(1, 240f32));
+                        cxt.column(Some(1f32));
+                        let flags = rg::TableFlags::Resizable | rg::TableFlags::Sortable | rg::TableFlags::RowBg | rg::TableFlags::BordersInnerV;
+                        if cxt.begin_table("assets", 2, flags) {
+                            cxt.table_setup_column("Name", rg::TableColumnFlags::DefaultSort, 0f32);
+                            cxt.table_setup_column("Size", rg::TableColumnFlags::PreferSortDescending, 80f32);
+                            cxt.table_headers_row();
+
+                            if let Some(specs) = cxt.table_sort_specs() {
+                                if specs.dirty {
+                                    match specs.column {
+                                        0 => assets.sort_by(|a, b| a.0.cmp(&b.0)),
+                                        _ => assets.sort_by_key(|a| a.1),
+                                    }
+                                    if specs.direction == rg::SortDirection::Descending {
+                                        assets.reverse();
+                                    }
+                                }
+                            }
+
+                            for (name, size) in &assets {
+                                cxt.table_next_row();
+                                cxt.table_next_column();
+                                cxt.paragraph(name);
+                                cxt.table_next_column();
+                                cxt.paragraph(&format!("{} KB", size));
+                            }
+                            cxt.end_table();
+                        }
                         
                         cxt.end_tab_item();
                     }
@@ -933,7 +1091,8 @@ fn main() {
                 cxt.end_panel();
             }
             
-            cxt.column(Some(0.65f32));
+            cxt.splitter_horizontal("split", &mut split_ratio);
+            cxt.column(None);
             if cxt.begin_panel("Right", rg::PanelFlags::Styled) {
                 cxt.row(rg::RowType::dynamic(2));
                 {
@@ -945,6 +1104,55 @@ fn main() {
                 cxt.row(rg::RowType::dynamic(2));
                 cxt.column(Some(0.3f32));
                 cxt.textfield("textfield", &mut text);
+                cxt.column(Some(0.2f32));
+                cxt.image_button(&checker);
+
+                let frame_times: Vec<f32> = (0..60)
+                    .map(|i| deltas[(frame + i) % 60] * 1000f32)
+                    .collect();
+                cxt.row(rg::RowType::dynamic(1));
+                cxt.column(Some(1f32));
+                cxt.set_next_plot_reference_lines(&[16.6f32]);
+                cxt.plot_lines(
+                    &format!("avg {:.3}ms, min {:.3}ms, max {:.3}ms", avg, min, max),
+                    &frame_times,
+                    Some(0f32),
+                    Some(60f32),
+                    float2(0f32, 80f32)
+                );
+
+                cxt.row(rg::RowType::dynamic(3));
+                cxt.column(Some(0.45f32));
+                cxt.progress_bar(Some((frame % 300) as f32 / 300f32), None);
+                cxt.column(Some(0.45f32));
+                cxt.progress_bar(None, Some("Loading"));
+                cxt.column(Some(0.1f32));
+                cxt.spinner(8f32);
+
+                cxt.row(rg::RowType::dynamic(1));
+                cxt.column(Some(1f32));
+                cxt.color_edit("Accent", &mut accent);
+
+                cxt.separator();
+                cxt.indent(16f32);
+                cxt.row(rg::RowType::dynamic(1));
+                cxt.column(Some(0.5f32));
+                cxt.progress_bar(Some(0.25f32), None);
+                cxt.same_line();
+                cxt.spinner(8f32);
+                cxt.unindent(16f32);
+
+                cxt.row(rg::RowType::dynamic(1));
+                cxt.column(Some(1f32));
+                if cxt.begin_list_box("Log", 160f32) {
+                    let item_height = cxt.selectable_height();
+                    let clipper = cxt.begin_list_clipper(log.len(), item_height);
+                    for i in clipper.range() {
+                        cxt.list_box_item(&log[i], i, &mut log_selection);
+                    }
+                    cxt.end_list_clipper(clipper);
+                    cxt.end_list_box();
+                }
                 /*cxt.row(rg::RowType::dynamic(2));
                 cxt.column(Some(1f32));
                 cxt.paragraph(&text);
@@ -953,7 +1161,10 @@ fn main() {
                 cxt.textfield("textfield2", &mut text);*/
                 cxt.end_panel();
             }
-            
+
+            cxt.anchored(rg::Anchor::BottomRight, float2(1f32, 1f32), float2(-8f32, -8f32), float2(90f32, 20f32));
+            cxt.paragraph("v0.1 overlay");
+
             cxt.end();
         }
 
@@ -962,43 +1173,6 @@ fn main() {
         cxt.draw();
         cxt.end_frame();
 
-        if cxt.io.is_key_down(0) {
-            {
-                let list = &mut cxt.draw_list;
-                list.clear();
-                
-                let dbg_area = Rect::new(float2(20f32, 20f32), float2(700f32, 300f32));
-                let dbg_inner = dbg_area.pad(5f32);
-                let dbg_w = dbg_inner.width();
-                let max_y = 60f32;
-
-                let bot_left = float2(dbg_inner.min.0, dbg_inner.max.1);
-                
-                list.add_rect_filled(dbg_area.min, dbg_area.max, 0f32, 0xbb000000);
-                list.add_rect_filled(dbg_inner.min, dbg_inner.max, 0f32, 0x22ffffff);
-                let y = bot_left.1 - (16.6f32 / max_y) * dbg_inner.height();
-                list.add_line(float2(bot_left.0, y), float2(dbg_inner.max.0, y), 0x55ffffff);
-                let mut path = list.path();
-
-                for (i, &d) in deltas.iter().enumerate() {
-                    let x = bot_left.0 + (i as f32 / 59f32 * dbg_w);
-                    let y = bot_left.1 - ((d * 1000f32) / max_y) * dbg_inner.height();
-                    
-                    let point = float2(x, y);
-
-                    path = path.line(point);
-                }
-                
-                path.stroke(1f32, false, 0xffffffff);
-            }
-            let list = &mut cxt.draw_list;
-                list.push_layer(10);
-
-            cxt.renderer.render(list);
-
-            list.clear();
-        }
-
         window.gl_swap_window();
     }
 }
@@ -50,14 +50,9 @@ impl IdStorage {
 
     fn lower_bound(&self, key: Id) -> usize {
         let len = self.pairs.len();
-        if len == 0 {
-            return 0;
-        }
         
         let mut first = 0;
-        let mut last = len - 1;
-
-        let mut count = last - first;
+        let mut count = len;
         while count > 0 {
             let count2 = count >> 1;
             let offset = first + count2;
@@ -95,6 +90,23 @@ impl IdStorage {
         }
     }
     
+    pub fn get_int(&self, id: Id) -> Option<i32> {
+        let i = self.lower_bound(id);
+
+        match self.pairs.get(i) {
+            Some(pair) if pair.key == id => Some(unsafe { pair.val.integer }),
+            _ => None,
+        }
+    }
+
+    pub fn get_float_ref(&mut self, id: Id, default_val: f32) -> *mut f32 {
+        unsafe {
+            &mut (*self.get_ref(id, StorageValue {
+                float: default_val,
+            })).float as *mut _
+        }
+    }
+    
     pub fn set_value(&mut self, id: Id, val: StorageValue) {
         let i = self.lower_bound(id);
         let len = self.pairs.len();
@@ -116,6 +128,13 @@ impl IdStorage {
             integer: val,
         });
     }
+
+    #[inline(always)]
+    pub fn set_float(&mut self, id: Id, val: f32) {
+        self.set_value(id, StorageValue {
+            float: val,
+        });
+    }
 }
 
 #[repr(transparent)]
@@ -170,6 +189,20 @@ impl<T: Default + Clone> Pool<T> {
         
         self.add()
     }
+
+    /// Like `get` but doesn't add an item when there's none for `id`.
+    pub fn find(&self, id: Id) -> Option<PoolIndex> {
+        match self.map.get_int(id) {
+            Some(idx) if idx != -1 => Some(PoolIndex(idx as usize)),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Pool<T> {
+    pub fn iter(&self) -> ::std::slice::Iter<T> {
+        self.data.iter()
+    }
 }
 
 impl<T> Index<PoolIndex> for Pool<T> {
@@ -202,6 +235,9 @@ fn pool() {
     assert_eq!(pool[a_2], 100);
     assert_eq!(pool[b], 200);
     assert_eq!(pool[c], 300);
+
+    assert_eq!(pool.find(0x2).map(|idx| pool[idx]), Some(200));
+    assert!(pool.find(0x4).is_none());
 }
 
 #[test]
@@ -226,3 +262,20 @@ fn storage() {
     assert_eq!(unsafe { *c }, 1024);
     assert_eq!(unsafe { *d }, 404);
 }
+
+#[test]
+fn storage_last_key() {
+    let mut storage = IdStorage::new();
+
+    storage.set_int(0x1, 1);
+    storage.set_int(0x2, 2);
+    storage.set_int(0x3, 3);
+    storage.set_int(0x4, 4);
+
+    assert_eq!(storage.get_int(0x4), Some(4));
+    assert_eq!(storage.get_int(0x5), None);
+
+    storage.set_int(0x4, 40);
+    assert_eq!(storage.get_int(0x4), Some(40));
+    assert_eq!(storage.pairs.len(), 4);
+}
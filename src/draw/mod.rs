@@ -7,18 +7,92 @@ use crate::math::*;
 
 pub type TextureHandle = *mut ();
 
+/// Clip rectangle used when nothing has been pushed, large enough to never
+/// clip anything.
+pub const NO_CLIP_RECT: float4 = float4(-8192f32, -8192f32, 8192f32, 8192f32);
+
+/// Layer position drawn on top of all windows, used for popups.
+pub const OVERLAY_LAYER: u32 = ::std::u32::MAX;
+
+/// Region of a texture, `region` is `[x, y, width, height]` in pixels of
+/// a texture sized `size`.
+#[derive(Copy, Clone)]
 pub struct Image {
     handle: TextureHandle,
     size: [u16; 2],
     region: [u16; 4],
 }
 
+impl Image {
+    /// Image covering the whole texture.
+    pub fn new(handle: TextureHandle, size: [u16; 2]) -> Self {
+        Image::with_region(handle, size, [0, 0, size[0], size[1]])
+    }
+
+    pub fn with_region(handle: TextureHandle, size: [u16; 2], region: [u16; 4]) -> Self {
+        Image {
+            handle,
+            size,
+            region,
+        }
+    }
+
+    pub fn handle(&self) -> TextureHandle {
+        self.handle
+    }
+
+    /// Size of the region in pixels.
+    pub fn size(&self) -> float2 {
+        float2(self.region[2] as f32, self.region[3] as f32)
+    }
+
+    /// Texture coordinates of the region.
+    pub fn uv(&self) -> Rect {
+        let w = self.size[0].max(1) as f32;
+        let h = self.size[1].max(1) as f32;
+        let r = &self.region;
+
+        Rect::new(
+            float2(r[0] as f32 / w, r[1] as f32 / h),
+            float2((r[0] + r[2]) as f32 / w, (r[1] + r[3]) as f32 / h)
+        )
+    }
+}
+
+/// Nine-slice image, `regions` is `[x, y, width, height, left, top, right,
+/// bottom]`: the region of a texture sized `size` followed by the widths of
+/// its fixed borders, all in pixels.
+#[derive(Copy, Clone)]
 pub struct DynamicImage {
     handle: TextureHandle,
     size: [u16; 2],
     regions: [u16; 8],
 }
 
+impl DynamicImage {
+    pub fn new(handle: TextureHandle, size: [u16; 2], region: [u16; 4], borders: [u16; 4]) -> Self {
+        DynamicImage {
+            handle,
+            size,
+            regions: [
+                region[0], region[1], region[2], region[3],
+                borders[0], borders[1], borders[2], borders[3],
+            ],
+        }
+    }
+
+    pub fn handle(&self) -> TextureHandle {
+        self.handle
+    }
+
+    /// Left, top, right and bottom border widths in pixels.
+    pub fn borders(&self) -> float4 {
+        let r = &self.regions;
+
+        float4(r[4] as f32, r[5] as f32, r[6] as f32, r[7] as f32)
+    }
+}
+
 #[derive(Copy, Clone)]
 #[repr(C)]
 pub struct Vertex {
@@ -43,12 +117,20 @@ pub trait Renderer {
     
     fn create_texture_a8(&mut self, width: u32, height: u32) -> (TextureHandle, TextureHandle);
     fn upload_a8(&mut self, handle: TextureHandle, x: u32, y: u32, width: u32, height: u32, data: &[u8], stride: u32);
+
+    /// Creates an RGBA texture from `width * height * 4` bytes of data, the
+    /// handle is used both for updates and in draw commands.
+    fn create_texture_rgba8(&mut self, width: u32, height: u32, data: &[u8]) -> TextureHandle;
+    /// Replaces a region of an RGBA texture, `stride` is in pixels.
+    fn update_texture(&mut self, handle: TextureHandle, x: u32, y: u32, width: u32, height: u32, data: &[u8], stride: u32);
+    fn destroy_texture(&mut self, handle: TextureHandle);
 }
 
 pub struct DrawCommand {
     pub index_offset: u32,
     pub index_count: u32,
-    clip_rect: float4,
+    /// Scissor rectangle as `(min_x, min_y, max_x, max_y)` in screen space.
+    pub clip_rect: float4,
     pub texture_id: TextureHandle,
 }
 
@@ -57,7 +139,7 @@ impl DrawCommand {
         DrawCommand {
             index_offset: 0,
             index_count: 0,
-            clip_rect: float4(0f32, 0f32, 800f32, 800f32),
+            clip_rect: NO_CLIP_RECT,
             texture_id: ::std::ptr::null_mut() as _
         }
     }
@@ -90,6 +172,7 @@ pub struct DrawList {
     layer_index: usize,
 
     clip_stack: Vec<float4>,
+    full_clip_rect: float4,
     texture_stack: Vec<TextureHandle>
 }
 
@@ -107,6 +190,7 @@ impl DrawList {
             layer_index: 0,
             
             clip_stack: Vec::new(),
+            full_clip_rect: NO_CLIP_RECT,
             texture_stack: Vec::new()
         }
     }
@@ -132,23 +216,64 @@ impl DrawList {
         self.push_draw_cmd();
     }
     
+    /// Sets the clip rectangle used when the clip stack is empty, usually
+    /// the whole display.
+    pub fn set_full_clip_rect(&mut self, rect: float4) {
+        self.full_clip_rect = rect;
+        self.update_clip_rect();
+    }
+    
+    /// Pushes a clip rectangle, intersected with the current one.
     pub fn push_clip_rect(&mut self, rect: float4) {
-        self.clip_stack.push(rect);
+        let current = self.current_clip_rect();
+        let min_x = rect.0.max(current.0);
+        let min_y = rect.1.max(current.1);
+        let max_x = rect.2.min(current.2).max(min_x);
+        let max_y = rect.3.min(current.3).max(min_y);
+        
+        self.clip_stack.push(float4(min_x, min_y, max_x, max_y));
+        self.update_clip_rect();
+    }
 
-        let current_rect = self.current_cmd().clip_rect;
-        //if current_rect != rect {
-        //    self.push_draw_cmd();
-        //}
+    /// Pushes the full clip rect, for content like tooltips that may go
+    /// outside of the current clip rect. Popped with `pop_clip_rect`.
+    pub fn push_full_clip_rect(&mut self) {
+        self.clip_stack.push(self.full_clip_rect);
+        self.update_clip_rect();
     }
 
     pub fn pop_clip_rect(&mut self) {
         self.clip_stack.pop();
+        self.update_clip_rect();
     }
 
-    pub fn set_texture(&mut self, texture: TextureHandle) {        
-        if self.current_cmd().texture_id == ::std::ptr::null_mut() {
-            self.current_cmd().texture_id = texture;
-        } else if self.current_cmd().texture_id != texture {
+    fn update_clip_rect(&mut self) {
+        let rect = self.current_clip_rect();
+        let cmd = self.current_cmd();
+
+        if cmd.clip_rect == rect {
+            return;
+        }
+
+        if cmd.index_count == 0 {
+            cmd.clip_rect = rect;
+        } else {
+            self.push_draw_cmd();
+        }
+    }
+
+    /// Binds `texture` for what's drawn next. Anything already in the
+    /// current command keeps its texture, so a change starts a new command
+    /// unless the current one is still empty.
+    pub fn set_texture(&mut self, texture: TextureHandle) {
+        let cmd = self.current_cmd();
+        if cmd.texture_id == texture {
+            return;
+        }
+
+        if cmd.index_count == 0 {
+            cmd.texture_id = texture;
+        } else {
             self.push_draw_cmd();
             self.current_cmd().texture_id = texture;
         }
@@ -174,12 +299,15 @@ impl DrawList {
         
         while i < len {
             let layer = unsafe { self.layers.get_unchecked_mut(i) };
-            layer.position = stack_positions[layer.position as usize] as u32;
+            if layer.position != OVERLAY_LAYER {
+                layer.position = stack_positions[layer.position as usize] as u32;
+            }
             
             i += 1;
         }
 
-        self.layers.sort_unstable_by_key(|l| l.position);
+        // stable, a window can be split into several layers around overlays
+        self.layers.sort_by_key(|l| l.position);
     }
     
     pub fn commands<'a>(&'a self) -> Vec<&'a [DrawCommand]> {
@@ -205,21 +333,19 @@ impl DrawList {
     }
 
     fn current_cmd(&mut self) -> &mut DrawCommand {
-        if self.commands.len() > 0 {
-            self.commands.last_mut().unwrap()
-        } else {
-            self.commands.push(DrawCommand::new());
-
-            self.commands.last_mut().unwrap()
+        if self.commands.len() == 0 {
+            self.push_draw_cmd();
         }
+
+        self.commands.last_mut().unwrap()
     }
 
     fn current_texture(&self) -> TextureHandle {
         self.texture_stack.last().cloned().unwrap_or(::std::ptr::null() as *const () as TextureHandle)
     }
 
-    fn current_clip_rect(&self) -> float4 {
-        self.clip_stack.last().cloned().unwrap_or(float4(0f32, 0f32, 0f32, 0f32))
+    pub fn current_clip_rect(&self) -> float4 {
+        self.clip_stack.last().cloned().unwrap_or(self.full_clip_rect)
     }
 
     pub fn add_line(&mut self, a: float2, b: float2, color: u32) {
@@ -275,6 +401,124 @@ impl DrawList {
         self.path_rect(a, b, rounding).fill(color);
     }
 
+    /// Filled rectangle with a color per corner, interpolated in between.
+    pub fn add_rect_filled_multicolor(&mut self, a: float2, b: float2, top_left: u32, top_right: u32, bottom_right: u32, bottom_left: u32) {
+        let uv = float2(0f32, 0f32);
+
+        self.vertices.push(Vertex::new(a, uv, top_left));
+        self.vertices.push(Vertex::new(float2(b.0, a.1), uv, top_right));
+        self.vertices.push(Vertex::new(b, uv, bottom_right));
+        self.vertices.push(Vertex::new(float2(a.0, b.1), uv, bottom_left));
+
+        let offset = self.index_offset as u16;
+        self.indices.push(offset + 0);
+        self.indices.push(offset + 1);
+        self.indices.push(offset + 2);
+        self.indices.push(offset + 0);
+        self.indices.push(offset + 2);
+        self.indices.push(offset + 3);
+
+        self.index_offset += 4;
+        self.current_cmd().index_count += 6;
+    }
+
+
+    /// Draws `uv` of `texture` stretched over `rect`, multiplied by `tint`.
+    pub fn add_image(&mut self, texture: TextureHandle, rect: Rect, uv: Rect, tint: u32) {
+        let prev_texture = self.current_cmd().texture_id;
+        self.set_texture(texture);
+        self.prim_rect_uv(rect, uv, tint);
+
+        // untextured shapes sample the white texel at 0,0 of the previous
+        // texture, so don't leave the image bound
+        self.set_texture(prev_texture);
+    }
+
+    /// Draws `image` over `rect` with fixed size corners, edges stretched
+    /// along one axis and the center stretched along both.
+    pub fn add_nine_slice(&mut self, image: &DynamicImage, rect: Rect, tint: u32) {
+        let r = &image.regions;
+        let tex_w = image.size[0].max(1) as f32;
+        let tex_h = image.size[1].max(1) as f32;
+        let borders = image.borders();
+
+        // shrink the borders evenly when the rect is too small for them
+        let scale_x = if borders.0 + borders.2 > rect.width() {
+            rect.width() / (borders.0 + borders.2)
+        } else {
+            1f32
+        };
+        let scale_y = if borders.1 + borders.3 > rect.height() {
+            rect.height() / (borders.1 + borders.3)
+        } else {
+            1f32
+        };
+
+        let xs = [
+            rect.min.0,
+            rect.min.0 + borders.0 * scale_x,
+            rect.max.0 - borders.2 * scale_x,
+            rect.max.0,
+        ];
+        let ys = [
+            rect.min.1,
+            rect.min.1 + borders.1 * scale_y,
+            rect.max.1 - borders.3 * scale_y,
+            rect.max.1,
+        ];
+
+        let (x, y, w, h) = (r[0] as f32, r[1] as f32, r[2] as f32, r[3] as f32);
+        let us = [
+            x / tex_w,
+            (x + borders.0) / tex_w,
+            (x + w - borders.2) / tex_w,
+            (x + w) / tex_w,
+        ];
+        let vs = [
+            y / tex_h,
+            (y + borders.1) / tex_h,
+            (y + h - borders.3) / tex_h,
+            (y + h) / tex_h,
+        ];
+
+        let prev_texture = self.current_cmd().texture_id;
+        self.set_texture(image.handle);
+
+        for row in 0..3 {
+            for col in 0..3 {
+                if xs[col + 1] <= xs[col] || ys[row + 1] <= ys[row] {
+                    continue;
+                }
+
+                self.prim_rect_uv(
+                    Rect::new(float2(xs[col], ys[row]), float2(xs[col + 1], ys[row + 1])),
+                    Rect::new(float2(us[col], vs[row]), float2(us[col + 1], vs[row + 1])),
+                    tint
+                );
+            }
+        }
+
+        self.set_texture(prev_texture);
+    }
+
+    fn prim_rect_uv(&mut self, rect: Rect, uv: Rect, tint: u32) {
+        let (a, b) = (rect.min, rect.max);
+        self.vertices.push(Vertex::new(a, uv.min, tint));
+        self.vertices.push(Vertex::new(float2(b.0, a.1), float2(uv.max.0, uv.min.1), tint));
+        self.vertices.push(Vertex::new(b, uv.max, tint));
+        self.vertices.push(Vertex::new(float2(a.0, b.1), float2(uv.min.0, uv.max.1), tint));
+
+        let offset = self.index_offset as u16;
+        self.indices.push(offset + 0);
+        self.indices.push(offset + 1);
+        self.indices.push(offset + 2);
+        self.indices.push(offset + 0);
+        self.indices.push(offset + 2);
+        self.indices.push(offset + 3);
+
+        self.index_offset += 4;
+        self.current_cmd().index_count += 6;
+    }
 
     pub fn add_poly_line(&mut self, thickness: f32, closed: bool, color_a: u32, color_b: u32) {
         // TEMP
@@ -404,11 +648,12 @@ impl<'a> PathBuilder<'a> {
     }
 
     pub fn arc(mut self, center: float2, radius: f32, min: f32, max: f32, segments: u32) -> Self {
-        if radius == 0f32 {
+        if radius == 0f32 || segments == 0 {
             self.list.path.push(center);
+            return self;
         }
 
-        for i in 0..(segments - 1) {
+        for i in 0..=segments {
             let a = min + (i as f32 / segments as f32) * (max - min);
             self.list.path.push(float2(center.0 + a.cos() * radius, center.1 + a.sin() * radius));
         }
@@ -435,3 +680,66 @@ impl<'a> PathBuilder<'a> {
         self.list.path.clear();
     }
 }
+
+#[test]
+fn clip_rect_stack() {
+    let mut list = DrawList::new();
+    list.set_full_clip_rect(float4(0f32, 0f32, 800f32, 600f32));
+
+    list.add_rect_filled(float2(0f32, 0f32), float2(10f32, 10f32), 0f32, 0xffffffff);
+    list.push_clip_rect(float4(10f32, 10f32, 200f32, 200f32));
+    list.push_clip_rect(float4(100f32, 0f32, 300f32, 150f32));
+    list.add_rect_filled(float2(0f32, 0f32), float2(10f32, 10f32), 0f32, 0xffffffff);
+    list.pop_clip_rect();
+    list.pop_clip_rect();
+    list.add_rect_filled(float2(0f32, 0f32), float2(10f32, 10f32), 0f32, 0xffffffff);
+
+    assert_eq!(list.commands.len(), 3);
+    assert_eq!(list.commands[0].clip_rect, float4(0f32, 0f32, 800f32, 600f32));
+    assert_eq!(list.commands[1].clip_rect, float4(100f32, 10f32, 200f32, 150f32));
+    assert_eq!(list.commands[2].clip_rect, float4(0f32, 0f32, 800f32, 600f32));
+}
+
+#[test]
+fn nine_slice() {
+    let mut list = DrawList::new();
+    let texture = 1usize as TextureHandle;
+    let image = DynamicImage::new(texture, [32, 32], [0, 0, 32, 32], [8, 8, 8, 8]);
+
+    list.add_nine_slice(&image, Rect::new(float2(0f32, 0f32), float2(100f32, 50f32)), 0xffffffff);
+    assert_eq!(list.vertices.len(), 9 * 4);
+    assert_eq!(list.commands.len(), 2);
+    assert_eq!(list.commands[0].texture_id, texture);
+    assert_eq!(list.commands[0].index_count, 9 * 6);
+
+    // corners keep their size, the center stretches
+    assert_eq!(list.vertices[4].pos, [8f32, 0f32]);
+    assert_eq!(list.vertices[6].pos, [92f32, 8f32]);
+    assert_eq!(list.vertices[6].uv, [24f32 / 32f32, 8f32 / 32f32]);
+}
+
+#[test]
+fn texture_after_shapes() {
+    let mut list = DrawList::new();
+    let texture = 1usize as TextureHandle;
+    let image = DynamicImage::new(texture, [32, 32], [0, 0, 32, 32], [8, 8, 8, 8]);
+    let rect = Rect::new(float2(0f32, 0f32), float2(100f32, 50f32));
+    let uv = Rect::new(float2(0f32, 0f32), float2(1f32, 1f32));
+
+    // the rect keeps the font texture instead of taking the image's
+    list.add_rect_filled(rect.min, rect.max, 0f32, 0xffffffff);
+    list.add_image(texture, rect, uv, 0xffffffff);
+    assert_eq!(list.commands.len(), 3);
+    assert!(list.commands[0].texture_id.is_null());
+    assert_eq!(list.commands[1].texture_id, texture);
+    assert_eq!(list.commands[1].index_count, 6);
+
+    let mut list = DrawList::new();
+    list.add_rect_filled(rect.min, rect.max, 0f32, 0xffffffff);
+    list.add_nine_slice(&image, rect, 0xffffffff);
+    assert_eq!(list.commands.len(), 3);
+    assert!(list.commands[0].texture_id.is_null());
+    assert_ne!(list.commands[0].index_count, 0);
+    assert_eq!(list.commands[1].texture_id, texture);
+    assert_eq!(list.commands[1].index_count, 9 * 6);
+}
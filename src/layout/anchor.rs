@@ -0,0 +1,114 @@
+use crate::{
+    Context,
+
+    math::{*},
+};
+
+/// Part of the panel a widget is anchored to. Point anchors place the
+/// widget relative to an edge or center, stretch anchors span the panel
+/// along one or both axes.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Anchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+
+    StretchTop,
+    StretchHorizontal,
+    StretchBottom,
+    StretchLeft,
+    StretchVertical,
+    StretchRight,
+    Stretch,
+}
+
+impl Anchor {
+    /// Corners of the anchor as ratios of the panel size.
+    pub fn range(self) -> (float2, float2) {
+        let point = |x: f32, y: f32| (float2(x, y), float2(x, y));
+
+        match self {
+            Anchor::TopLeft => point(0f32, 0f32),
+            Anchor::TopCenter => point(0.5f32, 0f32),
+            Anchor::TopRight => point(1f32, 0f32),
+            Anchor::CenterLeft => point(0f32, 0.5f32),
+            Anchor::Center => point(0.5f32, 0.5f32),
+            Anchor::CenterRight => point(1f32, 0.5f32),
+            Anchor::BottomLeft => point(0f32, 1f32),
+            Anchor::BottomCenter => point(0.5f32, 1f32),
+            Anchor::BottomRight => point(1f32, 1f32),
+
+            Anchor::StretchTop => (float2(0f32, 0f32), float2(1f32, 0f32)),
+            Anchor::StretchHorizontal => (float2(0f32, 0.5f32), float2(1f32, 0.5f32)),
+            Anchor::StretchBottom => (float2(0f32, 1f32), float2(1f32, 1f32)),
+            Anchor::StretchLeft => (float2(0f32, 0f32), float2(0f32, 1f32)),
+            Anchor::StretchVertical => (float2(0.5f32, 0f32), float2(0.5f32, 1f32)),
+            Anchor::StretchRight => (float2(1f32, 0f32), float2(1f32, 1f32)),
+            Anchor::Stretch => (float2(0f32, 0f32), float2(1f32, 1f32)),
+        }
+    }
+}
+
+/// Rect anchored to `bounds`, see `Context::anchored`.
+fn anchor_rect(bounds: Rect, anchor: Anchor, pivot: float2, offset: float2, size: float2) -> Rect {
+    let (amin, amax) = anchor.range();
+    let extent = bounds.max - bounds.min;
+
+    let axis = |bmin: f32, extent: f32, amin: f32, amax: f32, pivot: f32, offset: f32, size: f32| {
+        let lo = bmin + extent * amin;
+        let span = extent * (amax - amin);
+        let min = lo + offset - pivot * size;
+
+        (min, min + span + size)
+    };
+
+    let (x0, x1) = axis(bounds.min.0, extent.0, amin.0, amax.0, pivot.0, offset.0, size.0);
+    let (y0, y1) = axis(bounds.min.1, extent.1, amin.1, amax.1, pivot.1, offset.1, size.1);
+
+    Rect::new(float2(x0, y0), float2(x1, y1))
+}
+
+impl Context {
+    /// Places the next widget relative to the current panel instead of the
+    /// row, for overlays on `begin_root`. `pivot` is the point of the widget,
+    /// as ratios of its size, that sits on the anchor moved by `offset`.
+    /// Along stretched axes `size` is added to the span of the panel, so a
+    /// negative size leaves a margin.
+    ///
+    /// The rect follows the panel bounds of the current frame and scrolling
+    /// doesn't move it. The widget doesn't count towards the scrollable
+    /// content and the row layout is back to what it was after it.
+    pub fn anchored(&mut self, anchor: Anchor, pivot: float2, offset: float2, size: float2) -> Rect {
+        let panel = &mut self.panel_stack[self.current_panel];
+        let bounds = panel.bounds;
+        let rect = anchor_rect(bounds, anchor, pivot, offset, size);
+        if panel.anchored.is_none() {
+            panel.anchored = Some((panel.row.clone(), float2(panel.max_x, panel.max_y)));
+        }
+
+        self.layout_place_next(rect);
+
+        rect
+    }
+}
+
+#[test]
+fn anchor_rects() {
+    let bounds = Rect::new(float2(0f32, 0f32), float2(800f32, 600f32));
+    let corners = |rect: Rect| (rect.min.0, rect.min.1, rect.max.0, rect.max.1);
+
+    let rect = anchor_rect(bounds, Anchor::TopRight, float2(1f32, 0f32), float2(-10f32, 10f32), float2(100f32, 20f32));
+    assert_eq!(corners(rect), (690f32, 10f32, 790f32, 30f32));
+
+    let rect = anchor_rect(bounds, Anchor::BottomCenter, float2(0.5f32, 1f32), float2(0f32, 0f32), float2(200f32, 40f32));
+    assert_eq!(corners(rect), (300f32, 560f32, 500f32, 600f32));
+
+    let rect = anchor_rect(bounds, Anchor::StretchTop, float2(0.5f32, 0f32), float2(0f32, 0f32), float2(-20f32, 30f32));
+    assert_eq!(corners(rect), (10f32, 0f32, 790f32, 30f32));
+}
@@ -0,0 +1,372 @@
+use crate::{
+    Context,
+    PoolIndex,
+    RowLayout,
+
+    math::{*},
+};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum FlexDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// Placement of a child across the direction of its box.
+#[derive(Copy, Clone, PartialEq)]
+pub enum FlexAlign {
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FlexSize {
+    /// Size in pixels along the box.
+    Fixed(f32),
+    /// Share of the space left by the fixed children, by weight.
+    Flex(f32),
+}
+
+/// Size and alignment of the next child of a box, see `flex_item`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct FlexItem {
+    pub size: FlexSize,
+    pub min: f32,
+    pub max: f32,
+    /// Size across the box, horizontal boxes measure it from the widget when
+    /// it's `None`.
+    pub cross: Option<f32>,
+    /// Overrides the alignment of the box.
+    pub align: Option<FlexAlign>,
+}
+
+impl FlexItem {
+    pub fn fixed(size: f32) -> Self {
+        FlexItem {
+            size: FlexSize::Fixed(size),
+            min: 0f32,
+            max: ::std::f32::MAX,
+            cross: None,
+            align: None,
+        }
+    }
+
+    pub fn flex(weight: f32) -> Self {
+        FlexItem {
+            size: FlexSize::Flex(weight),
+            ..FlexItem::fixed(0f32)
+        }
+    }
+
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    pub fn cross(mut self, cross: f32) -> Self {
+        self.cross = Some(cross);
+        self
+    }
+
+    pub fn align(mut self, align: FlexAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+/// Box state kept between frames. Children are placed with the sizes
+/// solved from the previous frame's declarations against the current
+/// bounds, so a change in the children shows up one frame later.
+#[derive(Clone)]
+pub struct FlexBox {
+    direction: FlexDirection,
+    gap: f32,
+    align: FlexAlign,
+    bounds: Rect,
+    next_offset: f32,
+    saved_row: Option<RowLayout>,
+
+    items: Vec<FlexItem>,
+    last_items: Vec<FlexItem>,
+    measured: Vec<f32>,
+
+    sizes: Vec<f32>,
+    cross: Vec<f32>,
+    extent: f32,
+}
+
+impl FlexBox {
+    fn new() -> Self {
+        FlexBox {
+            direction: FlexDirection::Horizontal,
+            gap: 0f32,
+            align: FlexAlign::Stretch,
+            bounds: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
+            next_offset: 0f32,
+            saved_row: None,
+
+            items: Vec::new(),
+            last_items: Vec::new(),
+            measured: Vec::new(),
+
+            sizes: Vec::new(),
+            cross: Vec::new(),
+            extent: 0f32,
+        }
+    }
+
+    fn main_size(&self) -> f32 {
+        match self.direction {
+            FlexDirection::Horizontal => self.bounds.width(),
+            FlexDirection::Vertical => self.bounds.height(),
+        }
+    }
+
+    fn cross_size(&self) -> f32 {
+        match self.direction {
+            FlexDirection::Horizontal => self.bounds.height(),
+            FlexDirection::Vertical => self.bounds.width(),
+        }
+    }
+}
+
+impl Default for FlexBox {
+    fn default() -> Self {
+        FlexBox::new()
+    }
+}
+
+/// Sizes along the box for `items`. Flex children share what's left after
+/// the fixed ones and the gaps, children that hit their min or max keep it
+/// and the rest is shared again between the others.
+fn solve_flex(items: &[FlexItem], available: f32, gap: f32, sizes: &mut Vec<f32>) {
+    let clamp = |item: &FlexItem, size: f32| size.max(item.min).min(item.max.max(item.min));
+
+    sizes.clear();
+    let mut frozen = Vec::with_capacity(items.len());
+    let mut remaining = available - gap * items.len().saturating_sub(1) as f32;
+
+    for item in items {
+        match item.size {
+            FlexSize::Fixed(size) => {
+                let size = clamp(item, size);
+                remaining -= size;
+                sizes.push(size);
+                frozen.push(true);
+            }
+            FlexSize::Flex(_) => {
+                sizes.push(0f32);
+                frozen.push(false);
+            }
+        }
+    }
+
+    loop {
+        let weight: f32 = items.iter().zip(&frozen)
+            .filter(|&(_, &frozen)| !frozen)
+            .map(|(item, _)| match item.size { FlexSize::Flex(weight) => weight.max(0f32), _ => 0f32 })
+            .sum();
+
+        let mut clamped = false;
+        for (i, item) in items.iter().enumerate() {
+            if frozen[i] {
+                continue;
+            }
+
+            let share = match item.size {
+                FlexSize::Flex(w) if weight > 0f32 => remaining.max(0f32) * w.max(0f32) / weight,
+                _ => 0f32,
+            };
+            sizes[i] = share;
+
+            let size = clamp(item, share);
+            if size != share {
+                sizes[i] = size;
+                frozen[i] = true;
+                remaining -= size;
+                clamped = true;
+            }
+        }
+
+        if !clamped {
+            break;
+        }
+    }
+}
+
+impl Context {
+    /// Row of children laid out left to right in the next widget slot.
+    /// Declare each child with `flex_item` before submitting its widget.
+    pub fn begin_hbox(&mut self, id: &str, gap: f32, align: FlexAlign) {
+        self.begin_flex_box(id, FlexDirection::Horizontal, gap, align);
+    }
+
+    /// Column of children laid out top to bottom in the next widget slot.
+    /// In rows without an explicit height flex children get their minimum
+    /// size, since the box is only as tall as its content.
+    pub fn begin_vbox(&mut self, id: &str, gap: f32, align: FlexAlign) {
+        self.begin_flex_box(id, FlexDirection::Vertical, gap, align);
+    }
+
+    pub fn end_hbox(&mut self) {
+        self.end_flex_box();
+    }
+
+    pub fn end_vbox(&mut self) {
+        self.end_flex_box();
+    }
+
+    /// Places the next widget as a child of the current box.
+    pub fn flex_item(&mut self, item: FlexItem) {
+        let idx = match self.flex_stack.last() {
+            Some(&idx) => idx,
+            None => return,
+        };
+        self.flex_end_item(idx);
+
+        let flex = &mut self.flex_boxes[idx];
+        let i = flex.items.len();
+        flex.items.push(item);
+        flex.measured.push(0f32);
+
+        // children that weren't there last frame get the space left
+        let offset = flex.next_offset;
+        let main = match (flex.sizes.get(i), item.size) {
+            (Some(&size), _) => size,
+            (None, FlexSize::Fixed(size)) => size.max(item.min).min(item.max.max(item.min)),
+            (None, FlexSize::Flex(_)) => (flex.main_size() - offset).max(item.min).min(item.max.max(item.min)),
+        };
+        flex.next_offset += main + flex.gap;
+
+        let available = flex.cross_size();
+        let measured = match flex.direction {
+            FlexDirection::Horizontal => flex.cross.get(i).cloned().filter(|&c| c > 0f32),
+            FlexDirection::Vertical => None,
+        };
+        let cross = item.cross.or(measured).unwrap_or(available).min(available);
+        let (cross_offset, cross) = match item.align.unwrap_or(flex.align) {
+            FlexAlign::Start => (0f32, cross),
+            FlexAlign::Center => (((available - cross) * 0.5f32).round(), cross),
+            FlexAlign::End => (available - cross, cross),
+            FlexAlign::Stretch => (0f32, available),
+        };
+
+        let rect = match flex.direction {
+            FlexDirection::Horizontal => {
+                let min = flex.bounds.min + float2(offset, cross_offset);
+                Rect::new(min, min + float2(main, cross))
+            }
+            FlexDirection::Vertical => {
+                let min = flex.bounds.min + float2(cross_offset, offset);
+                Rect::new(min, min + float2(cross, main))
+            }
+        };
+
+        self.layout_place_next(rect);
+    }
+
+    fn begin_flex_box(&mut self, id: &str, direction: FlexDirection, gap: f32, align: FlexAlign) {
+        let id = self.id(id);
+        let idx = self.flex_boxes.get(id);
+
+        // in rows that grow to their content use what the box measured last
+        // frame, otherwise fill the slot
+        let auto_height = self.panel_stack[self.current_panel].row.auto_height;
+        let extent = self.flex_boxes[idx].extent;
+        let (bounds, _state) = if auto_height && extent > 0f32 {
+            self.widget(Some(extent))
+        } else {
+            self.widget(None)
+        };
+
+        let saved_row = self.panel_stack[self.current_panel].row.clone();
+
+        let flex = &mut self.flex_boxes[idx];
+        flex.direction = direction;
+        flex.gap = gap;
+        flex.align = align;
+        flex.bounds = bounds;
+        flex.next_offset = 0f32;
+        flex.saved_row = Some(saved_row);
+        flex.items.clear();
+        flex.measured.clear();
+
+        let available = flex.main_size();
+        let mut sizes = ::std::mem::replace(&mut flex.sizes, Vec::new());
+        solve_flex(&flex.last_items, available, gap, &mut sizes);
+        flex.sizes = sizes;
+
+        self.flex_stack.push(idx);
+    }
+
+    fn end_flex_box(&mut self) {
+        let idx = match self.flex_stack.pop() {
+            Some(idx) => idx,
+            None => return,
+        };
+        self.flex_end_item(idx);
+
+        let flex = &mut self.flex_boxes[idx];
+        flex.cross.clear();
+        flex.cross.extend_from_slice(&flex.measured);
+
+        // content size, flex children at their minimum
+        flex.extent = match flex.direction {
+            FlexDirection::Horizontal => flex.items.iter().zip(&flex.measured)
+                .map(|(item, &measured)| item.cross.unwrap_or(measured))
+                .fold(0f32, f32::max),
+            FlexDirection::Vertical => {
+                let mut sizes = Vec::with_capacity(flex.items.len());
+                solve_flex(&flex.items, 0f32, flex.gap, &mut sizes);
+                sizes.iter().sum::<f32>() + flex.gap * flex.items.len().saturating_sub(1) as f32
+            }
+        };
+        ::std::mem::swap(&mut flex.items, &mut flex.last_items);
+
+        if let Some(row) = flex.saved_row.take() {
+            self.panel_stack[self.current_panel].row = row;
+        }
+    }
+
+    /// Records how tall the widget of the current child wanted to be.
+    fn flex_end_item(&mut self, idx: PoolIndex) {
+        let measured = self.panel_stack[self.current_panel].row.item_height;
+
+        let flex = &mut self.flex_boxes[idx];
+        if let Some(last) = flex.measured.last_mut() {
+            *last = measured;
+        }
+    }
+}
+
+#[test]
+fn flex_solve() {
+    let items = [
+        FlexItem::fixed(80f32),
+        FlexItem::flex(1f32),
+        FlexItem::flex(2f32),
+    ];
+    let mut sizes = Vec::new();
+
+    solve_flex(&items, 388f32, 4f32, &mut sizes);
+    assert_eq!(sizes, vec![80f32, 100f32, 200f32]);
+
+    // the heavier child hits its max, the other one takes the rest
+    let items = [
+        FlexItem::fixed(80f32),
+        FlexItem::flex(1f32).min(50f32),
+        FlexItem::flex(2f32).max(120f32),
+    ];
+    solve_flex(&items, 388f32, 4f32, &mut sizes);
+    assert_eq!(sizes, vec![80f32, 180f32, 120f32]);
+
+    solve_flex(&items, 100f32, 4f32, &mut sizes);
+    assert_eq!(sizes, vec![80f32, 50f32, 0f32]);
+}
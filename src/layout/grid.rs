@@ -0,0 +1,287 @@
+use crate::{
+    Context,
+    PoolIndex,
+    RowLayout,
+
+    math::{*},
+};
+
+/// Size of a grid column or row.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GridTrack {
+    Pixels(f32),
+    /// Share of the space left by the other tracks, by weight.
+    Fraction(f32),
+    /// Size of the largest widget that sits in the track alone on the
+    /// previous frame. Columns use the width widgets report for their
+    /// content, so far text and buttons, the others count as 0 wide.
+    Auto,
+}
+
+#[derive(Clone)]
+pub struct Grid {
+    bounds: Rect,
+    saved_row: Option<RowLayout>,
+
+    columns: Vec<GridTrack>,
+    rows: Vec<GridTrack>,
+    column_pos: Vec<(f32, f32)>,
+    row_pos: Vec<(f32, f32)>,
+
+    current_row: Option<(usize, usize)>,
+    current_col: Option<(usize, usize)>,
+    measured: Vec<f32>,
+    last_measured: Vec<f32>,
+    col_measured: Vec<f32>,
+    last_col_measured: Vec<f32>,
+    extent: f32,
+}
+
+impl Grid {
+    fn new() -> Self {
+        Grid {
+            bounds: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
+            saved_row: None,
+
+            columns: Vec::new(),
+            rows: Vec::new(),
+            column_pos: Vec::new(),
+            row_pos: Vec::new(),
+
+            current_row: None,
+            current_col: None,
+            measured: Vec::new(),
+            last_measured: Vec::new(),
+            col_measured: Vec::new(),
+            last_col_measured: Vec::new(),
+            extent: 0f32,
+        }
+    }
+
+    fn cell_rect(&self, col: usize, row: usize, col_span: usize, row_span: usize) -> Option<Rect> {
+        let last_col = col + col_span.max(1) - 1;
+        let last_row = row + row_span.max(1) - 1;
+        if last_col >= self.column_pos.len() || last_row >= self.row_pos.len() {
+            return None;
+        }
+
+        let min = float2(self.column_pos[col].0, self.row_pos[row].0);
+        let max = float2(self.column_pos[last_col].1, self.row_pos[last_row].1);
+
+        Some(Rect::new(self.bounds.min + min, self.bounds.min + max))
+    }
+
+    /// Clears what this frame's cells asked for, the last frame's sizes
+    /// stay until `end_frame`.
+    fn begin_frame(&mut self) {
+        self.current_row = None;
+        self.current_col = None;
+
+        self.last_measured.resize(self.rows.len(), 0f32);
+        self.measured.clear();
+        self.measured.resize(self.rows.len(), 0f32);
+        self.last_col_measured.resize(self.columns.len(), 0f32);
+        self.col_measured.clear();
+        self.col_measured.resize(self.columns.len(), 0f32);
+    }
+
+    /// Records the size the widget of the current cell asked for, cells
+    /// spanning tracks don't size auto tracks.
+    fn end_cell(&mut self, width: f32, height: f32) {
+        if let Some((col, 1)) = self.current_col.take() {
+            self.col_measured[col] = self.col_measured[col].max(width);
+        }
+        if let Some((row, 1)) = self.current_row.take() {
+            self.measured[row] = self.measured[row].max(height);
+        }
+    }
+
+    fn end_frame(&mut self) {
+        ::std::mem::swap(&mut self.measured, &mut self.last_measured);
+        ::std::mem::swap(&mut self.col_measured, &mut self.last_col_measured);
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Grid::new()
+    }
+}
+
+/// Start and end offsets of `tracks` within `available`. Auto tracks use
+/// `measured` when given and are a fraction of 1 otherwise.
+fn resolve_tracks(tracks: &[GridTrack], available: f32, gap: f32, measured: Option<&[f32]>, out: &mut Vec<(f32, f32)>) {
+    let auto_size = |i: usize| measured.map(|m| m.get(i).cloned().unwrap_or(0f32));
+
+    let mut fixed = gap * tracks.len().saturating_sub(1) as f32;
+    let mut weight = 0f32;
+    for (i, track) in tracks.iter().enumerate() {
+        match (*track, auto_size(i)) {
+            (GridTrack::Pixels(size), _) => fixed += size,
+            (GridTrack::Auto, Some(size)) => fixed += size,
+            (GridTrack::Auto, None) => weight += 1f32,
+            (GridTrack::Fraction(w), _) => weight += w.max(0f32),
+        }
+    }
+
+    let remaining = (available - fixed).max(0f32);
+    let fraction = |w: f32| if weight > 0f32 { remaining * w.max(0f32) / weight } else { 0f32 };
+
+    out.clear();
+    let mut pos = 0f32;
+    for (i, track) in tracks.iter().enumerate() {
+        let size = match (*track, auto_size(i)) {
+            (GridTrack::Pixels(size), _) => size,
+            (GridTrack::Auto, Some(size)) => size,
+            (GridTrack::Auto, None) => fraction(1f32),
+            (GridTrack::Fraction(w), _) => fraction(w),
+        };
+
+        out.push((pos, pos + size));
+        pos += size + gap;
+    }
+}
+
+impl Context {
+    /// Grid in the next widget slot, place widgets with `grid_cell` and
+    /// finish with `end_grid`. In rows without an explicit height the grid
+    /// is as tall as its pixel and auto rows, fraction rows get nothing.
+    pub fn begin_grid(&mut self, id: &str, col_defs: &[GridTrack], row_defs: &[GridTrack]) {
+        let id = self.id(id);
+        let idx = self.grids.get(id);
+
+        let auto_height = self.panel_stack[self.current_panel].row.auto_height;
+        let extent = self.grids[idx].extent;
+        let (bounds, _state) = if auto_height && extent > 0f32 {
+            self.widget(Some(extent))
+        } else {
+            self.widget(None)
+        };
+
+        let gap = self.style.window.spacing;
+        let saved_row = self.panel_stack[self.current_panel].row.clone();
+
+        let grid = &mut self.grids[idx];
+        grid.bounds = bounds;
+        grid.saved_row = Some(saved_row);
+        grid.columns.clear();
+        grid.columns.extend_from_slice(col_defs);
+        grid.rows.clear();
+        grid.rows.extend_from_slice(row_defs);
+        grid.begin_frame();
+
+        let mut column_pos = ::std::mem::replace(&mut grid.column_pos, Vec::new());
+        let mut row_pos = ::std::mem::replace(&mut grid.row_pos, Vec::new());
+        resolve_tracks(&grid.columns, bounds.width(), gap.0, Some(&grid.last_col_measured), &mut column_pos);
+        resolve_tracks(&grid.rows, bounds.height(), gap.1, Some(&grid.last_measured), &mut row_pos);
+        grid.column_pos = column_pos;
+        grid.row_pos = row_pos;
+
+        self.grid_stack.push(idx);
+    }
+
+    pub fn end_grid(&mut self) {
+        let idx = match self.grid_stack.pop() {
+            Some(idx) => idx,
+            None => return,
+        };
+        self.grid_end_cell(idx);
+
+        let gap = self.style.window.spacing.1;
+        let grid = &mut self.grids[idx];
+        grid.end_frame();
+
+        // height without the fraction rows, for rows that size to content
+        grid.extent = grid.rows.iter().enumerate()
+            .map(|(i, track)| match *track {
+                GridTrack::Pixels(size) => size,
+                GridTrack::Auto => grid.last_measured[i],
+                GridTrack::Fraction(_) => 0f32,
+            })
+            .sum::<f32>() + gap * grid.rows.len().saturating_sub(1) as f32;
+
+        if let Some(row) = grid.saved_row.take() {
+            self.panel_stack[self.current_panel].row = row;
+        }
+    }
+
+    /// Makes the next widget fill the cells from `col`, `row` spanning
+    /// `col_span` columns and `row_span` rows. Out of range cells are
+    /// ignored.
+    pub fn grid_cell(&mut self, col: usize, row: usize, col_span: usize, row_span: usize) {
+        let idx = match self.grid_stack.last() {
+            Some(&idx) => idx,
+            None => return,
+        };
+        self.grid_end_cell(idx);
+
+        let rect = match self.grids[idx].cell_rect(col, row, col_span, row_span) {
+            Some(rect) => rect,
+            None => return,
+        };
+        let grid = &mut self.grids[idx];
+        grid.current_row = Some((row, row_span.max(1)));
+        grid.current_col = Some((col, col_span.max(1)));
+        let auto_col = col_span <= 1 && grid.columns[col] == GridTrack::Auto;
+
+        self.layout_place_next(rect);
+        self.panel_stack[self.current_panel].row.measure_width = auto_col;
+    }
+
+    /// Screen rect of a cell range of the current grid, for custom drawing.
+    pub fn grid_cell_rect(&self, col: usize, row: usize, col_span: usize, row_span: usize) -> Option<Rect> {
+        let idx = *self.grid_stack.last()?;
+
+        self.grids[idx].cell_rect(col, row, col_span, row_span)
+    }
+
+    fn grid_end_cell(&mut self, idx: PoolIndex) {
+        let row = &self.panel_stack[self.current_panel].row;
+        let (width, height) = (row.item_content_width, row.item_height);
+
+        self.grids[idx].end_cell(width, height);
+    }
+}
+
+#[test]
+fn grid_tracks() {
+    let tracks = [GridTrack::Pixels(100f32), GridTrack::Fraction(1f32), GridTrack::Fraction(3f32)];
+    let mut out = Vec::new();
+
+    resolve_tracks(&tracks, 504f32, 2f32, None, &mut out);
+    assert_eq!(out, vec![(0f32, 100f32), (102f32, 202f32), (204f32, 504f32)]);
+
+    let tracks = [GridTrack::Auto, GridTrack::Fraction(1f32)];
+    resolve_tracks(&tracks, 100f32, 0f32, Some(&[30f32]), &mut out);
+    assert_eq!(out, vec![(0f32, 30f32), (30f32, 100f32)]);
+
+    resolve_tracks(&tracks, 100f32, 0f32, None, &mut out);
+    assert_eq!(out, vec![(0f32, 50f32), (50f32, 100f32)]);
+}
+
+#[test]
+fn grid_auto_columns() {
+    let mut grid = Grid::new();
+    grid.columns = vec![GridTrack::Auto, GridTrack::Fraction(1f32)];
+    grid.rows = vec![GridTrack::Auto];
+
+    grid.begin_frame();
+    grid.current_col = Some((0, 1));
+    grid.current_row = Some((0, 1));
+    grid.end_cell(40f32, 20f32);
+    grid.current_col = Some((0, 1));
+    grid.current_row = Some((0, 1));
+    grid.end_cell(60f32, 10f32);
+    // spans two columns, doesn't size the auto one
+    grid.current_col = Some((0, 2));
+    grid.current_row = Some((0, 1));
+    grid.end_cell(150f32, 10f32);
+    grid.end_frame();
+
+    // the next frame sizes the columns with what the last one measured
+    grid.begin_frame();
+    let mut out = Vec::new();
+    resolve_tracks(&grid.columns, 200f32, 4f32, Some(&grid.last_col_measured), &mut out);
+    assert_eq!(out, vec![(0f32, 60f32), (64f32, 200f32)]);
+    assert_eq!(grid.last_measured, vec![20f32]);
+}
@@ -5,6 +5,14 @@ use crate::{
 };
 
 mod row;
+mod spacing;
+mod flex;
+mod grid;
+mod anchor;
 
 pub use self::row::*;
+pub use self::spacing::*;
+pub use self::flex::*;
+pub use self::grid::*;
+pub use self::anchor::*;
 
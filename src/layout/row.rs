@@ -13,6 +13,89 @@ pub enum RowType {
     StaticRow {
         max_height: Option<f32>,
     },
+    /// One column per ratio of the usable width, negative ratios share
+    /// what's left by the others.
+    Dynamic {
+        max_height: Option<f32>,
+        ratios: Vec<f32>,
+    },
+    /// One column per width in pixels.
+    Static {
+        max_height: Option<f32>,
+        widths: Vec<f32>,
+    },
+}
+
+/// Column of a row template.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RowTemplateColumn {
+    /// Fixed width in pixels.
+    Static(f32),
+    /// Shares the remaining space, can shrink to nothing.
+    Dynamic,
+    /// Shares the remaining space but doesn't get narrower than its minimum
+    /// width.
+    Variable(f32),
+}
+
+/// Solves template columns against `space`, the usable width of the row.
+fn solve_row_template(templates: &[RowTemplateColumn], space: f32, widths: &mut Vec<f32>) {
+    let mut min_fixed_width = 0f32;
+    let mut total_fixed_width = 0f32;
+    let mut max_variable_width = 0f32;
+    let mut variable_count = 0;
+    let mut dynamic_count = 0;
+
+    for template in templates {
+        match *template {
+            RowTemplateColumn::Static(width) => {
+                min_fixed_width += width;
+                total_fixed_width += width;
+            }
+            RowTemplateColumn::Variable(min_width) => {
+                total_fixed_width += min_width;
+                max_variable_width = max_variable_width.max(min_width);
+                variable_count += 1;
+            }
+            RowTemplateColumn::Dynamic => {
+                dynamic_count += 1;
+                variable_count += 1;
+            }
+        }
+    }
+
+    // when the variable columns can't all get their minimum width they keep
+    // it and the dynamic ones share what's left
+    let mut var_width = 0f32;
+    let mut enough_space = true;
+    if variable_count > 0 {
+        var_width = (space - min_fixed_width).max(0f32) / variable_count as f32;
+        enough_space = var_width >= max_variable_width;
+
+        if !enough_space {
+            var_width = if dynamic_count > 0 {
+                (space - total_fixed_width).max(0f32) / dynamic_count as f32
+            } else {
+                0f32
+            };
+        }
+    }
+
+    widths.clear();
+    widths.extend(templates.iter().map(|template| match *template {
+        RowTemplateColumn::Static(width) => width,
+        RowTemplateColumn::Variable(min_width) if !enough_space => min_width,
+        _ => var_width,
+    }));
+}
+
+/// Units of the rects passed to `layout_space_push`.
+#[derive(Copy, Clone, PartialEq)]
+pub enum LayoutFormat {
+    /// Ratios of the row width and height.
+    Dynamic,
+    /// Pixels.
+    Static,
 }
 
 
@@ -48,6 +131,20 @@ impl RowType {
             max_height: None,
         }
     }
+
+    pub fn dynamic_ratios(ratios: &[f32]) -> Self {
+        RowType::Dynamic {
+            max_height: None,
+            ratios: ratios.to_vec(),
+        }
+    }
+
+    pub fn static_widths(widths: &[f32]) -> Self {
+        RowType::Static {
+            max_height: None,
+            widths: widths.to_vec(),
+        }
+    }
 }
 
 impl Context {
@@ -68,6 +165,30 @@ impl Context {
 
                 (max_height, u32::max_value())
             }
+            RowType::Dynamic { max_height, ratios } => {
+                let (sum, undefined) = ratios.iter()
+                    .fold((0f32, 0), |(sum, n), &r| if r < 0f32 { (sum, n + 1) } else { (sum + r, n) });
+                let rest = if undefined > 0 {
+                    ((1f32 - sum) / undefined as f32).max(0f32)
+                } else {
+                    0f32
+                };
+
+                panel.row.ty = RowLayoutType::Dynamic;
+                panel.row.item_width = 0f32;
+                panel.row.ratios.clear();
+                panel.row.ratios.extend(ratios.iter().map(|&r| if r < 0f32 { rest } else { r }));
+
+                (max_height, ratios.len() as u32)
+            }
+            RowType::Static { max_height, widths } => {
+                panel.row.ty = RowLayoutType::Static;
+                panel.row.item_width = 0f32;
+                panel.row.ratios.clear();
+                panel.row.ratios.extend_from_slice(&widths);
+
+                (max_height, widths.len() as u32)
+            }
         };
 
         drop(panel);
@@ -118,8 +239,31 @@ impl Context {
                     panel.row.index = 0;
                 }
             },
-            RowLayoutType::DynamicFree => {},
-            RowLayoutType::Dynamic => {},
+            RowLayoutType::DynamicFree => {
+                let width = panel.bounds.width() - padding.0 * 2f32 - panel.indent;
+                let height = panel.row.height - spacing.1;
+                let item = panel.row.item;
+
+                let min = float2(
+                    panel.cursor.0 + padding.0 + panel.indent + item.min.0 * width,
+                    panel.cursor.1 + item.min.1 * height
+                );
+                let size = float2(item.width() * width, item.height() * height);
+
+                return Rect::new(min, min + size);
+            },
+            RowLayoutType::Dynamic => {
+                let ratio = panel.row.ratios.get(panel.row.index as usize).cloned().unwrap_or(0f32);
+                let w = ratio * panel_space;
+                item_offset = panel.row.item_offset;
+                item_width = w;
+                item_spacing = panel.row.index as f32 * spacing.0;
+
+                if modify {
+                    panel.row.item_offset += w;
+                    panel.row.filled += ratio;
+                }
+            },
             RowLayoutType::StaticFixed => {
                 item_width = panel.row.item_width;
                 item_offset = panel.row.index as f32 * item_width;
@@ -133,12 +277,28 @@ impl Context {
                     panel.row.item_offset += item_width + spacing.0;
                 }
             },
-            RowLayoutType::StaticFree => {},
-            RowLayoutType::Static => {},
+            RowLayoutType::StaticFree => {
+                let item = panel.row.item;
+                let min = float2(
+                    panel.cursor.0 + padding.0 + panel.indent + item.min.0,
+                    panel.cursor.1 + item.min.1
+                );
+
+                return Rect::new(min, min + float2(item.width(), item.height()));
+            },
+            RowLayoutType::Static => {
+                item_width = panel.row.ratios.get(panel.row.index as usize).cloned().unwrap_or(0f32);
+                item_offset = panel.row.item_offset;
+                item_spacing = panel.row.index as f32 * spacing.0;
+
+                if modify {
+                    panel.row.item_offset += item_width;
+                }
+            },
         }
 
         let origin = float2(
-            panel.cursor.0 + item_offset + item_spacing + padding.0,
+            panel.cursor.0 + item_offset + item_spacing + padding.0 + panel.indent,
             panel.cursor.1
         );
         let size = float2(item_width, panel.row.height - spacing.1);
@@ -194,3 +354,142 @@ impl Context {
         }
     }
 }
+
+impl Context {
+    /// Row of `height` where the next `widget_count` widgets are placed by
+    /// `layout_space_push` instead of by columns.
+    pub fn layout_space_begin(&mut self, format: LayoutFormat, height: f32, widget_count: u32) {
+        self.panel_layout(Some(height), widget_count, true);
+
+        let idx = self.current_panel;
+        let panel = &mut self.panel_stack[idx];
+        panel.row.ty = match format {
+            LayoutFormat::Dynamic => RowLayoutType::DynamicFree,
+            LayoutFormat::Static => RowLayoutType::StaticFree,
+        };
+        panel.row.max_height = panel.row.height;
+        panel.row.ratio = 0f32;
+        panel.row.filled = 0f32;
+        panel.row.item_width = 0f32;
+        panel.row.item_offset = 0f32;
+    }
+
+    /// Rect of the next widget relative to the top left of the row, in the
+    /// units given to `layout_space_begin`.
+    pub fn layout_space_push(&mut self, rect: Rect) {
+        let idx = self.current_panel;
+        let panel = &mut self.panel_stack[idx];
+
+        panel.row.item = rect;
+    }
+
+    /// Top left of the current row, what free row rects are relative to.
+    pub(crate) fn layout_row_origin(&self) -> float2 {
+        let panel = &self.panel_stack[self.current_panel];
+        let padding = panel.get_padding(&self.style.window);
+
+        float2(panel.cursor.0 + padding.0 + panel.indent, panel.cursor.1)
+    }
+
+    /// Makes the following widgets fill `rect`, without wrapping to a new
+    /// row. Used by containers that place their children themselves.
+    pub(crate) fn layout_place_next(&mut self, rect: Rect) {
+        let origin = self.layout_row_origin();
+
+        let panel = &mut self.panel_stack[self.current_panel];
+        panel.row.ty = RowLayoutType::StaticFree;
+        panel.row.index = 0;
+        panel.row.columns = u32::max_value();
+        panel.row.item_height = 0f32;
+        panel.row.measure_width = false;
+        panel.row.item_content_width = 0f32;
+        panel.row.item = Rect::new(rect.min - origin, rect.max - origin);
+    }
+
+    pub fn layout_space_end(&mut self) {
+        let idx = self.current_panel;
+        let panel = &mut self.panel_stack[idx];
+
+        panel.row.item_width = 0f32;
+        panel.row.item_height = 0f32;
+        panel.row.item_offset = 0f32;
+        panel.row.item = Rect::new(float2(0f32, 0f32), float2(0f32, 0f32));
+    }
+}
+
+impl Context {
+    /// Starts a row whose columns are declared with the `row_template_push_*`
+    /// functions and solved by `row_template_end`.
+    pub fn row_template_begin(&mut self, height: f32) {
+        self.panel_layout(Some(height), 1, true);
+
+        let idx = self.current_panel;
+        let panel = &mut self.panel_stack[idx];
+        panel.row.ty = RowLayoutType::Static;
+        panel.row.templates.clear();
+        panel.row.ratios.clear();
+        panel.row.columns = 0;
+        panel.row.ratio = 0f32;
+        panel.row.filled = 0f32;
+        panel.row.item_width = 0f32;
+        panel.row.item_offset = 0f32;
+    }
+
+    /// Column of `width` pixels.
+    pub fn row_template_push_static(&mut self, width: f32) {
+        self.row_template_push(RowTemplateColumn::Static(width));
+    }
+
+    /// Column taking a share of the remaining width.
+    pub fn row_template_push_dynamic(&mut self) {
+        self.row_template_push(RowTemplateColumn::Dynamic);
+    }
+
+    /// Column taking a share of the remaining width but at least `min_width`.
+    pub fn row_template_push_variable(&mut self, min_width: f32) {
+        self.row_template_push(RowTemplateColumn::Variable(min_width));
+    }
+
+    pub fn row_template_end(&mut self) {
+        let idx = self.current_panel;
+        let space = {
+            let panel = &self.panel_stack[idx];
+            panel.calculate_usable_space(&self.style.window)
+        };
+
+        let panel = &mut self.panel_stack[idx];
+        let row = &mut panel.row;
+        solve_row_template(&row.templates, space, &mut row.ratios);
+        row.index = 0;
+        row.item_offset = 0f32;
+    }
+
+    fn row_template_push(&mut self, template: RowTemplateColumn) {
+        let idx = self.current_panel;
+        let panel = &mut self.panel_stack[idx];
+
+        panel.row.templates.push(template);
+        panel.row.columns += 1;
+    }
+}
+
+#[test]
+fn row_templates() {
+    let templates = [
+        RowTemplateColumn::Static(120f32),
+        RowTemplateColumn::Dynamic,
+        RowTemplateColumn::Variable(60f32),
+        RowTemplateColumn::Static(30f32),
+    ];
+    let mut widths = Vec::new();
+
+    solve_row_template(&templates, 350f32, &mut widths);
+    assert_eq!(widths, vec![120f32, 100f32, 100f32, 30f32]);
+
+    // too narrow for the variable column to get an even share
+    solve_row_template(&templates, 250f32, &mut widths);
+    assert_eq!(widths, vec![120f32, 40f32, 60f32, 30f32]);
+
+    solve_row_template(&templates, 100f32, &mut widths);
+    assert_eq!(widths, vec![120f32, 0f32, 60f32, 30f32]);
+}
@@ -0,0 +1,100 @@
+use crate::{
+    Context,
+    RowLayoutType,
+
+    math::{*},
+};
+
+impl Context {
+    /// Horizontal line across the panel, on its own row.
+    pub fn separator(&mut self) {
+        let border = self.style.window.separator;
+        let height = border.thickness + self.style.window.padding.1 * 2f32;
+        let y = self.panel_skip(height);
+
+        let panel = &self.panel_stack[self.current_panel];
+        let padding = panel.get_padding(&self.style.window);
+        let min = float2(
+            panel.cursor.0 + padding.0 + panel.indent,
+            (y + (height - border.thickness) * 0.5f32).round()
+        );
+        let max = float2(
+            panel.cursor.0 + panel.bounds.width() - padding.0,
+            min.1 + border.thickness
+        );
+
+        self.draw_list.add_rect_filled(min, max, 0f32, border.color);
+    }
+
+    /// Vertical gap of `height`, ends the current row.
+    pub fn spacing(&mut self, height: f32) {
+        self.panel_skip(height);
+    }
+
+    /// Moves all following widgets of the panel right by `width`.
+    pub fn indent(&mut self, width: f32) {
+        let panel = &mut self.panel_stack[self.current_panel];
+
+        panel.indent += width;
+    }
+
+    pub fn unindent(&mut self, width: f32) {
+        let panel = &mut self.panel_stack[self.current_panel];
+
+        panel.indent = (panel.indent - width).max(0f32);
+    }
+
+    /// Places the next widget right after the previous one, with the width
+    /// of the next column, or of the last one when the row is full.
+    pub fn same_line(&mut self) {
+        let panel = &mut self.panel_stack[self.current_panel];
+
+        panel.row.same_line = true;
+    }
+
+    /// Takes up space like a widget without drawing anything. The width is
+    /// only used by static rows, dynamic rows decide it themselves.
+    pub fn dummy(&mut self, size: float2) {
+        let idx = self.current_panel;
+        let panel = &mut self.panel_stack[idx];
+
+        let item_width = panel.row.item_width;
+        let is_static = match panel.row.ty {
+            RowLayoutType::StaticFixed
+            | RowLayoutType::StaticRow
+            | RowLayoutType::StaticFree
+            | RowLayoutType::Static => true,
+            _ => false,
+        };
+        if is_static {
+            panel.row.item_width = size.0;
+        }
+
+        self.widget(Some(size.1));
+
+        if is_static {
+            self.panel_stack[idx].row.item_width = item_width;
+        }
+    }
+
+    /// Closes the current row and moves the cursor down by `height`, the row
+    /// layout itself is kept. Returns where the gap starts.
+    pub(crate) fn panel_skip(&mut self, height: f32) -> f32 {
+        let panel = &mut self.panel_stack[self.current_panel];
+
+        if panel.row.index > 0 {
+            panel.cursor.1 += panel.row.max_height;
+            panel.row.index = 0;
+            panel.row.max_height = 0f32;
+            panel.row.item_offset = 0f32;
+            panel.row.filled = 0f32;
+            panel.row.same_line = false;
+        }
+
+        let y = panel.cursor.1;
+        panel.cursor.1 += height;
+        panel.max_y = panel.max_y.max(panel.cursor.1);
+
+        y
+    }
+}
@@ -7,6 +7,7 @@ mod widget;
 mod layout;
 mod style;
 mod collections;
+mod settings;
 
 pub use self::math::*;
 pub use self::draw::*;
@@ -14,6 +15,7 @@ pub use self::widget::*;
 pub use self::layout::*;
 pub use self::style::*;
 pub use self::collections::*;
+pub use self::settings::*;
 
 use self::math::*;
 
@@ -79,13 +81,36 @@ pub struct Context {
     current_panel: usize,
     panel_index: usize,
     panel_stack: Vec<Panel>,
+    panel_saves: Vec<PanelSave>,
+    panel_mismatches: u32,
     id_stack: Vec<Id>,
     pub tab_bars: Pool<TabBar>,
     pub current_tab_bar: Vec<PoolIndex>,
+    tables: Pool<Table>,
+    current_table: Vec<PoolIndex>,
+    flex_boxes: Pool<FlexBox>,
+    flex_stack: Vec<PoolIndex>,
+    grids: Pool<Grid>,
+    grid_stack: Vec<PoolIndex>,
     tab_sorter: Vec<TabItemSort>,
+    next_window: NextWindowData,
+    scroll_states: Pool<ScrollState>,
+    scroll_consumed: bool,
+    dock_nodes: Vec<DockNode>,
+    dock_drag: Option<usize>,
+    storage: IdStorage,
+    next_plot_references: Vec<f32>,
+    color_popup: Id,
+    color_popup_pos: float2,
     
     pub prev_cursor: CursorType,
     pub cursor: CursorType,
+
+    /// Seconds to wait after a layout change before `want_save_settings`
+    /// reports true, `None` disables auto-saving.
+    pub settings_save_interval: Option<f32>,
+    settings_dirty_timer: f32,
+    want_save_settings: bool,
 }
 
 impl Context {
@@ -120,13 +145,34 @@ impl Context {
             current_panel: 0,
             panel_index: 0,
             panel_stack: Vec::new(),
+            panel_saves: Vec::new(),
+            panel_mismatches: 0,
             id_stack: Vec::new(),
             tab_bars: Pool::new(),
             current_tab_bar: Vec::new(),
+            tables: Pool::new(),
+            current_table: Vec::new(),
+            flex_boxes: Pool::new(),
+            flex_stack: Vec::new(),
+            grids: Pool::new(),
+            grid_stack: Vec::new(),
             tab_sorter: Vec::new(),
+            next_window: NextWindowData::new(),
+            scroll_states: Pool::new(),
+            scroll_consumed: false,
+            dock_nodes: Vec::new(),
+            dock_drag: None,
+            storage: IdStorage::new(),
+            next_plot_references: Vec::new(),
+            color_popup: 0,
+            color_popup_pos: float2(0f32, 0f32),
             
             prev_cursor: CursorType::Default,
             cursor: CursorType::Default,
+
+            settings_save_interval: None,
+            settings_dirty_timer: 0f32,
+            want_save_settings: false,
         }
     }
 
@@ -145,7 +191,12 @@ impl Context {
             seconds + ms
         };
         self.io.delta = delta;
+        self.io.time += delta;
         self.now = Instant::now();
+        self.scroll_consumed = false;
+
+        let display = self.io.display_size;
+        self.draw_list.set_full_clip_rect(float4(0f32, 0f32, display.0, display.1));
         
         if self.is_editing_text() {
             for action in self.io.text_edit_actions.drain(..) {
@@ -159,12 +210,14 @@ impl Context {
     
     pub fn end_frame(&mut self) {
         self.frame += 1;
+        self.io.update_clicks();
+        self.update_settings_timer();
 
         if self.prev_cursor != self.cursor {
             self.io.cursor = Some(self.cursor);
         }
 
-        self.panel_index = 0;
+        self.panel_end_frame();
         self.prev_cursor = self.cursor;
         self.cursor = CursorType::Default;
     }
@@ -186,10 +239,6 @@ impl Context {
         self.text_edit_state.id != 0
     }
 
-    pub fn set_next_window_pos(&mut self, pos: float2) {
-
-    }
-
     pub fn text(&mut self, text: &str) {
 
     }
@@ -214,17 +263,25 @@ pub enum MouseButton {
     Right = 3,
 }
 
+const DOUBLE_CLICK_TIME: f32 = 0.30f32;
+const DOUBLE_CLICK_DISTANCE: f32 = 6f32;
+
 pub struct IoState {
     pub display_size: float2,
     pub delta: f32,
+    pub time: f32,
 
     pub mouse_pressed: [bool; 5],
     pub mouse_down: [bool; 5],
     pub mouse_released: [bool; 5],
     pub mouse_clicked_pos: [float2; 5],
+    pub mouse_last_click_time: [f32; 5],
+    pub mouse_last_click_pos: [float2; 5],
     pub mouse: float2,
     pub mouse_delta: float2,
     pub mouse_scroll: float2,
+    pub key_ctrl: bool,
+    pub key_shift: bool,
     pub pressed: [bool; 512],
     pub down: [bool; 512],
 
@@ -238,15 +295,20 @@ impl IoState {
         IoState {
             display_size: float2(0f32, 0f32),
             delta: 0f32,
+            time: 0f32,
             pressed: [false; 512],
             down: [false; 512],
             mouse_pressed: [false; 5],
             mouse_down: [false; 5],
             mouse_released: [false; 5],
             mouse_clicked_pos: [float2(0f32, 0f32); 5],
+            mouse_last_click_time: [-1000f32; 5],
+            mouse_last_click_pos: [float2(0f32, 0f32); 5],
             mouse: float2(0f32, 0f32),
             mouse_delta: float2(0f32, 0f32),
             mouse_scroll: float2(0f32, 0f32),
+            key_ctrl: false,
+            key_shift: false,
 
             text_edit_actions: Vec::new(),
             cursor: None,
@@ -294,4 +356,30 @@ impl IoState {
     pub fn is_mouse_released(&self, button: MouseButton) -> bool {
         unsafe { *self.mouse_released.get_unchecked(button as usize) }
     }
+
+    /// Whether the button was pressed this frame close enough in time and
+    /// position to the previous press to count as a double click.
+    pub fn is_mouse_double_clicked(&self, button: MouseButton) -> bool {
+        let idx = button as usize;
+        
+        self.mouse_pressed[idx]
+            && self.time - self.mouse_last_click_time[idx] < DOUBLE_CLICK_TIME
+            && (self.mouse_clicked_pos[idx] - self.mouse_last_click_pos[idx]).length() < DOUBLE_CLICK_DISTANCE
+    }
+
+    fn update_clicks(&mut self) {
+        for idx in 0..5 {
+            if !self.mouse_pressed[idx] {
+                continue;
+            }
+
+            if self.time - self.mouse_last_click_time[idx] < DOUBLE_CLICK_TIME {
+                // don't let a third click count as another double click
+                self.mouse_last_click_time[idx] = -1000f32;
+            } else {
+                self.mouse_last_click_time[idx] = self.time;
+                self.mouse_last_click_pos[idx] = self.mouse_clicked_pos[idx];
+            }
+        }
+    }
 }
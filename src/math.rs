@@ -58,12 +58,23 @@ impl Rect {
     }
 
     pub fn clip(&self, other: Rect) -> Option<Rect> {
-        unimplemented!()
+        let min = float2(self.min.0.max(other.min.0), self.min.1.max(other.min.1));
+        let max = float2(self.max.0.min(other.max.0), self.max.1.min(other.max.1));
+
+        if min.0 < max.0 && min.1 < max.1 {
+            Some(Rect::new(min, max))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_float4(&self) -> float4 {
+        float4(self.min.0, self.min.1, self.max.0, self.max.1)
     }
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct float4(pub f32, pub f32, pub f32, pub f32);
 
 impl Add for float4 {
@@ -0,0 +1,220 @@
+use crate::{
+    Context, Id, WindowFlags, hash_id,
+
+    math::{
+        float2,
+        Rect,
+    },
+};
+
+use std::fmt::Write;
+
+pub struct SettingsSection<'a> {
+    pub ty: &'a str,
+    pub name: &'a str,
+    pub entries: Vec<(&'a str, &'a str)>,
+}
+
+/// Parses the ini-style text written by `Context::save_settings` into
+/// `[Type][Name]` sections of `key=value` entries.
+pub fn parse_settings(text: &str) -> Vec<SettingsSection> {
+    let mut sections: Vec<SettingsSection> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            let inner = &line[1..line.len() - 1];
+            if let Some(split) = inner.find("][") {
+                sections.push(SettingsSection {
+                    ty: &inner[..split],
+                    name: &inner[split + 2..],
+                    entries: Vec::new(),
+                });
+            }
+
+            continue;
+        }
+
+        if let (Some(section), Some(split)) = (sections.last_mut(), line.find('=')) {
+            section.entries.push((line[..split].trim(), line[split + 1..].trim()));
+        }
+    }
+
+    sections
+}
+
+fn parse_float2(val: &str) -> Option<float2> {
+    let mut parts = val.split(',');
+    let x = parts.next()?.trim().parse().ok()?;
+    let y = parts.next()?.trim().parse().ok()?;
+
+    Some(float2(x, y))
+}
+
+fn parse_bool(val: &str) -> bool {
+    val == "1"
+}
+
+pub fn parse_id(val: &str) -> Option<Id> {
+    let hex = val.trim_start_matches("0x");
+
+    u32::from_str_radix(hex, 16).ok()
+}
+
+impl Context {
+    /// Writes window layout and widget state to a string that can be
+    /// handed back to `load_settings` on the next run.
+    pub fn save_settings(&mut self) -> String {
+        let mut out = String::new();
+
+        for (order, &idx) in self.window_stack.iter().enumerate() {
+            let wnd = &self.windows[idx];
+            if wnd.flags.contains(WindowFlags::Background) {
+                continue;
+            }
+
+            let size = wnd.bounds.max - wnd.bounds.min;
+            let _ = writeln!(out, "[Window][{}]", wnd.name);
+            let _ = writeln!(out, "Pos={},{}", wnd.bounds.min.0, wnd.bounds.min.1);
+            let _ = writeln!(out, "Size={},{}", size.0, size.1);
+            let _ = writeln!(out, "Collapsed={}", wnd.flags.contains(WindowFlags::Minimized) as u32);
+            let _ = writeln!(out, "Hidden={}", wnd.flags.contains(WindowFlags::Hidden) as u32);
+            let _ = writeln!(out, "Order={}", order);
+            out.push('\n');
+        }
+
+        self.save_tab_bar_settings(&mut out);
+        self.save_dock_settings(&mut out);
+
+        self.want_save_settings = false;
+        self.settings_dirty_timer = 0f32;
+
+        out
+    }
+
+    pub fn load_settings(&mut self, text: &str) {
+        let mut order = Vec::new();
+
+        for section in parse_settings(text) {
+            match section.ty {
+                "Window" => {
+                    let idx = self.load_window_settings(&section);
+                    let pos = section.entries.iter()
+                        .find(|&&(key, _)| key == "Order")
+                        .and_then(|&(_, val)| val.parse::<usize>().ok());
+
+                    if let Some(pos) = pos {
+                        order.push((pos, idx));
+                    }
+                },
+                "TabBar" => self.load_tab_bar_settings(&section),
+                "DockNode" => self.load_dock_settings(&section),
+                _ => {}
+            }
+        }
+
+        self.dock_fixup_windows();
+
+        // restore z-order, windows without settings keep their place at the back
+        order.sort_by_key(|&(pos, _)| pos);
+        for (_, idx) in order {
+            self.move_window_to_front(idx);
+        }
+    }
+
+    fn load_window_settings(&mut self, section: &SettingsSection) -> usize {
+        let hash = hash_id(section.name);
+        let (is_new, idx) = if let Some(idx) = self.find_window_hash(hash) {
+            (false, idx)
+        } else {
+            let bounds = Rect::new(float2(0f32, 0f32), float2(0f32, 0f32));
+            let idx = self.create_window(section.name, hash, bounds, WindowFlags::ReadOnly);
+            self.window_stack.push(idx);
+
+            (true, idx)
+        };
+
+        let wnd = &mut self.windows[idx];
+        let mut pos = None;
+        let mut size = None;
+
+        for &(key, val) in &section.entries {
+            match key {
+                "Pos" => pos = parse_float2(val).or(pos),
+                "Size" => size = parse_float2(val).or(size),
+                "Collapsed" => wnd.flags.set(WindowFlags::Minimized, parse_bool(val)),
+                "Hidden" => wnd.flags.set(WindowFlags::Hidden, parse_bool(val)),
+                _ => {}
+            }
+        }
+
+        // windows that don't exist yet take what's missing from `begin`
+        if is_new {
+            wnd.missing_pos = pos.is_none();
+            wnd.missing_size = size.is_none();
+        }
+
+        let pos = pos.unwrap_or(wnd.bounds.min);
+        let size = size.unwrap_or(wnd.bounds.max - wnd.bounds.min);
+        wnd.bounds = Rect::new(pos, pos + float2(size.0.max(0f32), size.1.max(0f32)));
+
+        idx
+    }
+
+    /// Starts the auto-save timer if one is configured and not already
+    /// running.
+    pub fn mark_settings_dirty(&mut self) {
+        if let Some(interval) = self.settings_save_interval {
+            if self.settings_dirty_timer <= 0f32 {
+                self.settings_dirty_timer = interval.max(::std::f32::EPSILON);
+            }
+        }
+    }
+
+    /// True once settings changed and the auto-save interval has passed,
+    /// until `save_settings` is called.
+    pub fn want_save_settings(&self) -> bool {
+        self.want_save_settings
+    }
+
+    pub(crate) fn update_settings_timer(&mut self) {
+        if self.settings_dirty_timer > 0f32 {
+            self.settings_dirty_timer -= self.io.delta;
+
+            if self.settings_dirty_timer <= 0f32 {
+                self.want_save_settings = true;
+            }
+        }
+    }
+}
+
+#[test]
+fn settings_parse() {
+    let text = "
+[Window][Tools]
+Pos=10,20.5
+Size=300,400
+Collapsed=1
+
+; comment
+[TabBar][0x0000beef]
+Selected=0x00001234
+";
+
+    let sections = parse_settings(text);
+    assert_eq!(sections.len(), 2);
+
+    assert_eq!(sections[0].ty, "Window");
+    assert_eq!(sections[0].name, "Tools");
+    assert_eq!(sections[0].entries.len(), 3);
+    assert_eq!(sections[0].entries[0], ("Pos", "10,20.5"));
+    assert_eq!(parse_float2(sections[0].entries[0].1).map(|v| v.1), Some(20.5f32));
+
+    assert_eq!(sections[1].ty, "TabBar");
+    assert_eq!(parse_id(sections[1].name), Some(0xbeef));
+    assert_eq!(parse_id(sections[1].entries[0].1), Some(0x1234));
+}
@@ -1,5 +1,8 @@
 use crate::{
-    draw::TextureHandle,
+    draw::{
+        DynamicImage,
+        TextureHandle,
+    },
     
     math::{*}
 };
@@ -14,6 +17,83 @@ pub const fn make_color(r: u8, g: u8, b: u8, a: u8) -> UColor {
     (r as u32)
 }
 
+/// Unpacks a `make_color` color into `[r, g, b, a]` in 0..1.
+pub fn color_to_rgba(color: UColor) -> [f32; 4] {
+    [
+        (color & 0xff) as f32 / 255f32,
+        ((color >> 8) & 0xff) as f32 / 255f32,
+        ((color >> 16) & 0xff) as f32 / 255f32,
+        ((color >> 24) & 0xff) as f32 / 255f32,
+    ]
+}
+
+pub fn rgba_to_color(rgba: [f32; 4]) -> UColor {
+    let c = |v: f32| (v.max(0f32).min(1f32) * 255f32).round() as u8;
+
+    make_color(c(rgba[0]), c(rgba[1]), c(rgba[2]), c(rgba[3]))
+}
+
+/// Converts RGB in 0..1 to hue, saturation and value in 0..1.
+pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let h = if delta <= 0f32 {
+        0f32
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6f32) / 6f32
+    } else if max == g {
+        ((b - r) / delta + 2f32) / 6f32
+    } else {
+        ((r - g) / delta + 4f32) / 6f32
+    };
+    let s = if max > 0f32 { delta / max } else { 0f32 };
+
+    [h, s, max]
+}
+
+/// Converts hue, saturation and value in 0..1 to RGB in 0..1.
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    let h = (h.fract() + 1f32).fract() * 6f32;
+    let sector = h.floor();
+    let f = h - sector;
+
+    let p = v * (1f32 - s);
+    let q = v * (1f32 - s * f);
+    let t = v * (1f32 - s * (1f32 - f));
+
+    match sector as u32 {
+        0 => [v, t, p],
+        1 => [q, v, p],
+        2 => [p, v, t],
+        3 => [p, q, v],
+        4 => [t, p, v],
+        _ => [v, p, q],
+    }
+}
+
+/// Formats a color as `#RRGGBBAA`.
+pub fn color_to_hex(color: UColor) -> String {
+    format!(
+        "#{:02X}{:02X}{:02X}{:02X}",
+        color & 0xff, (color >> 8) & 0xff, (color >> 16) & 0xff, color >> 24
+    )
+}
+
+/// Parses `#RRGGBB` or `#RRGGBBAA`, the `#` is optional.
+pub fn parse_hex_color(text: &str) -> Option<UColor> {
+    let hex = text.trim().trim_start_matches('#');
+    if (hex.len() != 6 && hex.len() != 8) || !hex.is_ascii() {
+        return None;
+    }
+
+    let byte = |i: usize| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok();
+    let a = if hex.len() == 8 { byte(3)? } else { 0xff };
+
+    Some(make_color(byte(0)?, byte(1)?, byte(2)?, a))
+}
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum CursorType {
     Default,
@@ -87,6 +167,8 @@ pub enum Background {
     NinePatch(TextureHandle, [float2; 4]),
     Texture(TextureHandle),
     Color(UColor),
+    /// Image with fixed corners and stretched edges and center.
+    NineSlice(DynamicImage),
 }
 
 pub struct WindowHeaderStyle {
@@ -127,6 +209,10 @@ pub struct WindowStyle {
     pub padding: float2,
     pub panel_padding: float2,
     pub scrollbar_size: float2,
+    pub min_size: float2,
+    /// Added above and below the font height for rows without a height.
+    pub min_row_height_padding: f32,
+    pub separator: Border,
 
     pub normal_border: Border,
     pub hover_border: Border,
@@ -145,6 +231,9 @@ impl WindowStyle {
             padding: float2(0f32, 0f32),
             panel_padding: float2(4f32, 4f32),
             scrollbar_size: float2(10f32, 10f32),
+            min_size: float2(64f32, 32f32),
+            min_row_height_padding: 2f32,
+            separator: Border::default_border(),
             
             normal_border: Border::default_border(),
             hover_border: Border::default_border(),
@@ -227,6 +316,11 @@ impl TabStyle {
             active: Background::Color(0),
         }
     }
+
+    /// Height of a tab, used by tab bars and dock tab strips.
+    pub fn tab_height(&self) -> f32 {
+        20f32 + self.padding.1 * 2f32
+    }
 }
 pub struct TextfieldStyle {
     pub padding: float2,
@@ -264,6 +358,170 @@ impl TextfieldStyle {
     }
 }
 
+pub struct ScrollbarStyle {
+    pub padding: float2,
+
+    pub normal_border: Border,
+    pub hover_border: Border,
+    pub active_border: Border,
+
+    pub background: Background,
+    pub normal: Background,
+    pub hover: Background,
+    pub active: Background,
+}
+
+impl ScrollbarStyle {
+    pub fn new() -> Self {
+        ScrollbarStyle {
+            padding: float2(0f32, 0f32),
+
+            normal_border: Border::default_border(),
+            hover_border: Border::default_border(),
+            active_border: Border::default_border(),
+
+            background: Background::Color(0),
+            normal: Background::Color(0),
+            hover: Background::Color(0),
+            active: Background::Color(0),
+        }
+    }
+}
+
+pub struct PlotStyle {
+    pub padding: float2,
+
+    pub text: TextStyle,
+    pub border: Border,
+    pub background: Background,
+
+    pub line: UColor,
+    pub line_hover: UColor,
+    pub histogram: UColor,
+    pub histogram_hover: UColor,
+    pub reference: UColor,
+
+    pub tooltip_text: TextStyle,
+    pub tooltip_background: UColor,
+}
+
+impl PlotStyle {
+    pub fn new() -> Self {
+        PlotStyle {
+            padding: float2(0f32, 0f32),
+
+            text: TextStyle::default_style(),
+            border: Border::default_border(),
+            background: Background::Color(0),
+
+            line: make_color(255, 255, 255, 255),
+            line_hover: make_color(255, 255, 255, 255),
+            histogram: make_color(255, 255, 255, 255),
+            histogram_hover: make_color(255, 255, 255, 255),
+            reference: make_color(255, 255, 255, 128),
+
+            tooltip_text: TextStyle::default_style(),
+            tooltip_background: make_color(0, 0, 0, 255),
+        }
+    }
+}
+
+pub struct ProgressStyle {
+    pub padding: float2,
+
+    pub text: TextStyle,
+    pub border: Border,
+    pub background: Background,
+    pub bar: Background,
+
+    pub spinner: UColor,
+    pub spinner_thickness: f32,
+}
+
+impl ProgressStyle {
+    pub fn new() -> Self {
+        ProgressStyle {
+            padding: float2(0f32, 0f32),
+
+            text: TextStyle::default_style(),
+            border: Border::default_border(),
+            background: Background::Color(0),
+            bar: Background::Color(0),
+
+            spinner: make_color(255, 255, 255, 255),
+            spinner_thickness: 2f32,
+        }
+    }
+}
+
+pub struct SelectableStyle {
+    pub padding: float2,
+
+    pub normal_text: TextStyle,
+    pub hover_text: TextStyle,
+    pub selected_text: TextStyle,
+
+    pub normal: Background,
+    pub hover: Background,
+    pub active: Background,
+    pub selected: Background,
+
+    pub list_background: Background,
+    pub list_border: Border,
+}
+
+impl SelectableStyle {
+    pub fn new() -> Self {
+        SelectableStyle {
+            padding: float2(0f32, 0f32),
+
+            normal_text: TextStyle::default_style(),
+            hover_text: TextStyle::default_style(),
+            selected_text: TextStyle::default_style(),
+
+            normal: Background::Color(0),
+            hover: Background::Color(0),
+            active: Background::Color(0),
+            selected: Background::Color(0),
+
+            list_background: Background::Color(0),
+            list_border: Border::default_border(),
+        }
+    }
+}
+
+pub struct TableStyle {
+    pub cell_padding: float2,
+
+    pub header_text: TextStyle,
+    pub header: Background,
+    pub header_hover: Background,
+    pub header_active: Background,
+    pub sort_arrow: UColor,
+
+    pub row_bg: UColor,
+    pub row_bg_alt: UColor,
+    pub border: Border,
+}
+
+impl TableStyle {
+    pub fn new() -> Self {
+        TableStyle {
+            cell_padding: float2(0f32, 0f32),
+
+            header_text: TextStyle::default_style(),
+            header: Background::Color(0),
+            header_hover: Background::Color(0),
+            header_active: Background::Color(0),
+            sort_arrow: make_color(255, 255, 255, 255),
+
+            row_bg: 0,
+            row_bg_alt: 0,
+            border: Border::default_border(),
+        }
+    }
+}
+
 pub struct ParagraphStyle {
     pub padding: float2,
 
@@ -286,6 +544,11 @@ pub struct Style {
     pub tab: TabStyle,
     pub textfield: TextfieldStyle,
     pub paragraph: ParagraphStyle,
+    pub scrollbar: ScrollbarStyle,
+    pub plot: PlotStyle,
+    pub progress: ProgressStyle,
+    pub selectable: SelectableStyle,
+    pub table: TableStyle,
 }
 
 const ACTIVE_DARK_BTN_BG: UColor = make_color(39, 42, 44, 255);
@@ -316,6 +579,11 @@ impl Style {
             tab: TabStyle::new(),
             textfield: TextfieldStyle::new(),
             paragraph: ParagraphStyle::new(),
+            scrollbar: ScrollbarStyle::new(),
+            plot: PlotStyle::new(),
+            progress: ProgressStyle::new(),
+            selectable: SelectableStyle::new(),
+            table: TableStyle::new(),
         }
     }
 
@@ -336,6 +604,7 @@ impl Style {
 
         style.padding = float2(2f32, 2f32);
         style.spacing = float2(2f32, 0f32);
+        style.separator = Border::new(make_color(20, 20, 20, 255), 1f32, 0f32);
 
         let window_bg = Background::Color(NORMAL_DARK_BG);
         style.normal = window_bg;
@@ -429,6 +698,99 @@ impl Style {
         style
     }
     
+    pub fn dark_scrollbar() -> ScrollbarStyle {
+        let mut style = ScrollbarStyle::new();
+
+        style.padding = float2(2f32, 2f32);
+
+        style.background = Background::Color(make_color(50, 50, 56, 255));
+        style.active = Background::Color(HOVER_DARK_BTN_BG);
+        style.hover = Background::Color(HOVER_DARK_BTN_BG);
+        style.normal = Background::Color(NORMAL_DARK_BTN_BG);
+
+        style.active_border = Border::new(make_color(10, 10, 10, 255), 1f32, 0f32);
+        style.hover_border = Border::new(make_color(30, 30, 30, 255), 1f32, 0f32);
+        style.normal_border = Border::new(make_color(20, 20, 20, 255), 1f32, 0f32);
+
+        style
+    }
+    
+    pub fn dark_plot() -> PlotStyle {
+        let mut style = PlotStyle::new();
+
+        style.padding = float2(4f32, 4f32);
+
+        style.text.color = NORMAL_DARK_TEXT_FADE;
+        style.text.align = TextAlignment::Centered;
+        style.border = Border::new(make_color(20, 20, 20, 255), 1f32, 0f32);
+        style.background = Background::Color(ACTIVE_DARK_TFIELD_BG);
+
+        style.line = make_color(156, 156, 156, 255);
+        style.line_hover = make_color(255, 110, 89, 255);
+        style.histogram = make_color(230, 179, 0, 255);
+        style.histogram_hover = make_color(255, 153, 0, 255);
+        style.reference = make_color(255, 255, 255, 60);
+
+        style.tooltip_text.color = NORMAL_DARK_TEXT;
+        style.tooltip_background = make_color(20, 20, 24, 240);
+
+        style
+    }
+    
+    pub fn dark_progress() -> ProgressStyle {
+        let mut style = ProgressStyle::new();
+
+        style.padding = float2(2f32, 2f32);
+
+        style.text.color = NORMAL_DARK_TEXT;
+        style.text.align = TextAlignment::Centered;
+        style.border = Border::new(make_color(20, 20, 20, 255), 1f32, 0f32);
+        style.background = Background::Color(ACTIVE_DARK_TFIELD_BG);
+        style.bar = Background::Color(make_color(230, 179, 0, 255));
+
+        style.spinner = make_color(230, 179, 0, 255);
+        style.spinner_thickness = 3f32;
+
+        style
+    }
+    
+    pub fn dark_selectable() -> SelectableStyle {
+        let mut style = SelectableStyle::new();
+
+        style.padding = float2(4f32, 2f32);
+
+        style.normal_text.color = NORMAL_DARK_TEXT_FADE;
+        style.hover_text.color = NORMAL_DARK_TEXT;
+        style.selected_text.color = NORMAL_DARK_TEXT;
+
+        style.hover = Background::Color(NORMAL_DARK_BG_TINT);
+        style.active = Background::Color(NORMAL_DARK_BTN_BG);
+        style.selected = Background::Color(make_color(0, 92, 153, 255));
+
+        style.list_background = Background::Color(NORMAL_DARK_TFIELD_BG);
+        style.list_border = Border::new(make_color(20, 20, 20, 255), 1f32, 0f32);
+
+        style
+    }
+    
+    pub fn dark_table() -> TableStyle {
+        let mut style = TableStyle::new();
+
+        style.cell_padding = float2(4f32, 2f32);
+
+        style.header_text.color = NORMAL_DARK_TEXT;
+        style.header = Background::Color(NORMAL_DARK_BG_TINT);
+        style.header_hover = Background::Color(HOVER_DARK_BTN_BG);
+        style.header_active = Background::Color(NORMAL_DARK_BTN_BG);
+        style.sort_arrow = NORMAL_DARK_TEXT_FADE;
+
+        style.row_bg = make_color(0, 0, 0, 0);
+        style.row_bg_alt = make_color(255, 255, 255, 10);
+        style.border = Border::new(make_color(30, 30, 30, 255), 1f32, 0f32);
+
+        style
+    }
+    
     pub fn dark_style() -> Self {
         let mut style = Style::new();
 
@@ -437,7 +799,31 @@ impl Style {
         style.tab = Self::dark_tabs();
         style.textfield = Self::dark_textfield();
         style.paragraph = Self::dark_paragraph();
+        style.scrollbar = Self::dark_scrollbar();
+        style.plot = Self::dark_plot();
+        style.progress = Self::dark_progress();
+        style.selectable = Self::dark_selectable();
+        style.table = Self::dark_table();
 
         style
     }
 }
+
+#[test]
+fn color_conversions() {
+    let color = make_color(255, 128, 0, 200);
+    assert_eq!(rgba_to_color(color_to_rgba(color)), color);
+
+    assert_eq!(color_to_hex(color), "#FF8000C8");
+    assert_eq!(parse_hex_color("#FF8000C8"), Some(color));
+    assert_eq!(parse_hex_color("ff8000"), Some(make_color(255, 128, 0, 255)));
+    assert_eq!(parse_hex_color("#ff80"), None);
+
+    let rgba = color_to_rgba(color);
+    let hsv = rgb_to_hsv(rgba[0], rgba[1], rgba[2]);
+    let rgb = hsv_to_rgb(hsv[0], hsv[1], hsv[2]);
+    assert_eq!(rgba_to_color([rgb[0], rgb[1], rgb[2], rgba[3]]), color);
+
+    let blue = hsv_to_rgb(2f32 / 3f32, 1f32, 1f32);
+    assert_eq!(rgba_to_color([blue[0], blue[1], blue[2], 1f32]), make_color(0, 0, 255, 255));
+}
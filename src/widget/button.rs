@@ -13,6 +13,7 @@ use crate::{
 
 use super::{
     WidgetState,
+    WidgetLayoutState,
 };
 
 bitflags!{
@@ -53,6 +54,9 @@ impl Context {
                 self.draw_list.add_rect_filled(inner.min, inner.max, border.rounding, col);
                 self.draw_list.add_rect(bounds.min, bounds.max, border.rounding, border.thickness, border.color);
             },
+            Background::NineSlice(image) => {
+                self.draw_list.add_nine_slice(&image, bounds, 0xffffffff);
+            },
             _ => {}
         }
     }
@@ -85,13 +89,19 @@ impl Context {
     pub fn button_text(&mut self, text: &str) -> bool {
         // TODO: maybe make wrapping optional
         self.last_widget_state = WidgetState::None;
-        
-        let w = self.peek_widget_width();
-        let h = self.button_height(text, w);
-           
-        let (bounds, state) = self.widget(Some(h));
+        self.widget_content_width(|cxt| cxt.button_width(text));
+
+        let h = if self.widget_below_clip() {
+            self.default_font.height() + self.style.button.padding.1 * 2f32
+        } else {
+            let w = self.peek_widget_width();
+            self.button_height(text, w)
+        };
 
-        // dbg!(bounds);
+        let (bounds, state) = self.widget(Some(h));
+        if state == WidgetLayoutState::Hidden {
+            return false;
+        }
 
         self.do_button_text(bounds, text)
     }
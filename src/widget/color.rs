@@ -0,0 +1,438 @@
+use crate::{
+    Context, MouseButton,
+    ButtonFlags,
+    CursorType,
+    Id,
+    TextAlignment,
+    UColor,
+    color_to_hex,
+    color_to_rgba,
+    hash_id_seed,
+    hsv_to_rgb,
+    make_color,
+    parse_hex_color,
+    rgb_to_hsv,
+    rgba_to_color,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+use super::{
+    WidgetState,
+    WidgetLayoutState,
+};
+
+const COLOR_BAR_WIDTH: f32 = 14f32;
+const COLOR_PICKER_SPACING: f32 = 4f32;
+const COLOR_PICKER_SIZE: float2 = float2(220f32, 160f32);
+
+#[derive(Copy, Clone, PartialEq)]
+enum ColorEditMode {
+    Rgba = 0,
+    Hsva = 1,
+    Hex = 2,
+}
+
+impl ColorEditMode {
+    fn from_i32(val: i32) -> Self {
+        match val {
+            1 => ColorEditMode::Hsva,
+            2 => ColorEditMode::Hex,
+            _ => ColorEditMode::Rgba,
+        }
+    }
+
+    fn next(self) -> Self {
+        ColorEditMode::from_i32((self as i32 + 1) % 3)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ColorEditMode::Rgba => "RGB",
+            ColorEditMode::Hsva => "HSV",
+            ColorEditMode::Hex => "HEX",
+        }
+    }
+}
+
+fn with_alpha(color: UColor, alpha: u8) -> UColor {
+    (color & 0x00ff_ffff) | (alpha as u32) << 24
+}
+
+/// `parse_hex_color` on the edit buffer of the hex field, without
+/// collecting it into a string.
+fn parse_hex_chars(chars: &[char]) -> Option<UColor> {
+    let mut bytes = [0u8; 9];
+    if chars.len() > bytes.len() {
+        return None;
+    }
+
+    for (byte, &ch) in bytes.iter_mut().zip(chars) {
+        if !ch.is_ascii() {
+            return None;
+        }
+        *byte = ch as u8;
+    }
+
+    parse_hex_color(::std::str::from_utf8(&bytes[..chars.len()]).ok()?)
+}
+
+impl Context {
+    /// Color swatch followed by editable RGBA, HSVA or hex fields. Clicking
+    /// the swatch opens a picker popup, the mode button cycles the fields.
+    pub fn color_edit(&mut self, label: &str, color: &mut UColor) -> bool {
+        let id = self.id(label);
+        let mode_id = hash_id_seed("##mode", id);
+        let hue_id = hash_id_seed("##hue", id);
+        let hex_id = hash_id_seed("##hex", id);
+
+        let pad = self.style.textfield.padding.1;
+        let height = self.default_font.height() + pad;
+        let (bounds, state) = self.widget(Some(height));
+        // an open popup or hex field still needs the widget
+        if state == WidgetLayoutState::Hidden && self.color_popup != id && self.active_id != hex_id {
+            return false;
+        }
+
+        let mode = ColorEditMode::from_i32(unsafe { *self.storage.get_int_ref(mode_id, 0) });
+        let mode_width = self.default_font.text_width(&mut *self.renderer, "HSV") + 8f32;
+        let label_width = self.default_font.text_width(&mut *self.renderer, label);
+        let spacing = COLOR_PICKER_SPACING;
+
+        let swatch = Rect::new(bounds.min, bounds.min + float2(height, height));
+        let label_bounds = Rect::new(float2(bounds.max.0 - label_width, bounds.min.1), bounds.max);
+        let mode_bounds = Rect::new(
+            float2(label_bounds.min.0 - spacing - mode_width, bounds.min.1),
+            float2(label_bounds.min.0 - spacing, bounds.max.1)
+        );
+        let fields = Rect::new(
+            float2(swatch.max.0 + spacing, bounds.min.1),
+            float2(mode_bounds.min.0 - spacing, bounds.max.1)
+        );
+
+        let mut changed = false;
+
+        // swatch, toggles the picker popup
+        self.last_widget_state = WidgetState::None;
+        if self.button_behaviour(swatch, ButtonFlags::PressOnClick) {
+            if self.color_popup == id {
+                self.color_popup = 0;
+            } else {
+                self.color_popup = id;
+                self.color_popup_pos = float2(swatch.min.0, swatch.max.1 + spacing);
+            }
+        }
+        self.draw_color_swatch(swatch, *color);
+
+        match mode {
+            ColorEditMode::Rgba | ColorEditMode::Hsva => {
+                let rgba = color_to_rgba(*color);
+                let stored_hue = self.storage.get_float_ref(hue_id, 0f32);
+                let mut values = if mode == ColorEditMode::Rgba {
+                    rgba
+                } else {
+                    let hsv = rgb_to_hsv(rgba[0], rgba[1], rgba[2]);
+                    // hue is lost for greys, keep the last one
+                    let hue = if hsv[1] > 0f32 && hsv[2] > 0f32 { hsv[0] } else { unsafe { *stored_hue } };
+                    [hue, hsv[1], hsv[2], rgba[3]]
+                };
+                let prefixes = if mode == ColorEditMode::Rgba {
+                    ["R", "G", "B", "A"]
+                } else {
+                    ["H", "S", "V", "A"]
+                };
+
+                let width = (fields.width() - spacing * 3f32) / 4f32;
+                let mut edited = false;
+                for i in 0..4 {
+                    let min = float2(fields.min.0 + i as f32 * (width + spacing), fields.min.1);
+                    let field = Rect::new(min, float2(min.0 + width, fields.max.1));
+                    edited |= self.color_component(field, prefixes[i], &mut values[i]);
+                }
+
+                if edited {
+                    let rgba = if mode == ColorEditMode::Rgba {
+                        values
+                    } else {
+                        unsafe { *self.storage.get_float_ref(hue_id, 0f32) = values[0]; }
+                        let rgb = hsv_to_rgb(values[0], values[1], values[2]);
+                        [rgb[0], rgb[1], rgb[2], values[3]]
+                    };
+
+                    let new_color = rgba_to_color(rgba);
+                    changed = new_color != *color;
+                    *color = new_color;
+                }
+            }
+            ColorEditMode::Hex => {
+                let text = color_to_hex(*color);
+                self.textfield_ex(hex_id, fields, &text);
+
+                if self.active_id == hex_id {
+                    if let Some(new_color) = parse_hex_chars(self.text_edit_state.chars()) {
+                        changed = new_color != *color;
+                        *color = new_color;
+                    }
+                }
+            }
+        }
+
+        // mode button
+        self.last_widget_state = WidgetState::None;
+        if self.button_behaviour(mode_bounds, ButtonFlags::None) {
+            unsafe { *self.storage.get_int_ref(mode_id, 0) = mode.next() as i32; }
+        }
+        let style = &self.style.button;
+        let state = self.last_widget_state;
+        let (text, border, background) = if state.contains(WidgetState::Active) {
+            (style.active_text, style.active_border, style.active)
+        } else if state.contains(WidgetState::Hovering) {
+            (style.hover_text, style.hover_border, style.hover)
+        } else {
+            (style.normal_text, style.normal_border, style.normal)
+        };
+        self.draw_button(mode_bounds, border, background);
+        self.color_centered_text(mode.label(), mode_bounds, text.color);
+
+        let text = self.style.paragraph.normal_text;
+        let y = bounds.min.1 + ((height - self.default_font.advance_y()) * 0.5f32).round();
+        self.draw_list.add_text_wrapped(
+            &mut *self.renderer,
+            &mut self.default_font,
+            label,
+            float2(label_bounds.min.0, y),
+            TextAlignment::Left,
+            label_bounds.width() + 1f32,
+            text.color
+        );
+
+        if self.color_popup == id {
+            changed |= self.color_picker_popup(swatch, color);
+        }
+
+        changed
+    }
+
+    /// Saturation/value square with hue and alpha bars, filling the next
+    /// widget slot.
+    pub fn color_picker(&mut self, label: &str, color: &mut UColor) -> bool {
+        let id = self.id(label);
+        let (bounds, state) = self.widget(Some(COLOR_PICKER_SIZE.1));
+        if state == WidgetLayoutState::Hidden {
+            return false;
+        }
+
+        self.color_picker_area(id, bounds, color)
+    }
+
+    fn color_picker_popup(&mut self, swatch: Rect, color: &mut UColor) -> bool {
+        let id = self.color_popup;
+        let padding = float2(6f32, 6f32);
+        let popup = Rect::new(self.color_popup_pos, self.color_popup_pos + COLOR_PICKER_SIZE + padding * 2f32);
+
+        // close when clicking anywhere else
+        let io = &self.io;
+        if io.is_mouse_pressed(MouseButton::Left) && !popup.contains(io.mouse) && !swatch.contains(io.mouse) {
+            self.color_popup = 0;
+            return false;
+        }
+
+        self.begin_overlay();
+
+        let window = &self.style.window;
+        let border = window.normal_border;
+        let background = window.normal;
+        self.draw_button(popup, border, background);
+
+        let area = popup.pad_sides(padding.0, padding.1, padding.0, padding.1);
+        let changed = self.color_picker_area(id, area, color);
+
+        self.end_overlay();
+
+        changed
+    }
+
+    fn color_picker_area(&mut self, id: Id, bounds: Rect, color: &mut UColor) -> bool {
+        let hue_id = hash_id_seed("##hue", id);
+
+        let bars_width = (COLOR_BAR_WIDTH + COLOR_PICKER_SPACING) * 2f32;
+        let size = bounds.height().min(bounds.width() - bars_width).max(0f32);
+        let sv = Rect::new(bounds.min, bounds.min + float2(size, size));
+        let hue_min = float2(sv.max.0 + COLOR_PICKER_SPACING, sv.min.1);
+        let hue_bar = Rect::new(hue_min, hue_min + float2(COLOR_BAR_WIDTH, size));
+        let alpha_min = float2(hue_bar.max.0 + COLOR_PICKER_SPACING, sv.min.1);
+        let alpha_bar = Rect::new(alpha_min, alpha_min + float2(COLOR_BAR_WIDTH, size));
+
+        let rgba = color_to_rgba(*color);
+        let mut hsv = rgb_to_hsv(rgba[0], rgba[1], rgba[2]);
+        if hsv[1] <= 0f32 || hsv[2] <= 0f32 {
+            hsv[0] = unsafe { *self.storage.get_float_ref(hue_id, 0f32) };
+        }
+        let mut alpha = rgba[3];
+
+        let io = &self.io;
+        let dragging = |rect: Rect| io.is_mouse_down(MouseButton::Left) && io.has_mouse_click_in_rect(MouseButton::Left, rect);
+        let t = |rect: Rect| float2(
+            ((io.mouse.0 - rect.min.0) / rect.width().max(1f32)).max(0f32).min(1f32),
+            ((io.mouse.1 - rect.min.1) / rect.height().max(1f32)).max(0f32).min(1f32)
+        );
+
+        let mut edited = false;
+        if dragging(sv) {
+            let t = t(sv);
+            hsv[1] = t.0;
+            hsv[2] = 1f32 - t.1;
+            edited = true;
+        } else if dragging(hue_bar) {
+            hsv[0] = t(hue_bar).1.min(0.9999f32);
+            edited = true;
+        } else if dragging(alpha_bar) {
+            alpha = 1f32 - t(alpha_bar).1;
+            edited = true;
+        }
+
+        let mut changed = false;
+        if edited {
+            unsafe { *self.storage.get_float_ref(hue_id, 0f32) = hsv[0]; }
+
+            let rgb = hsv_to_rgb(hsv[0], hsv[1], hsv[2]);
+            let new_color = rgba_to_color([rgb[0], rgb[1], rgb[2], alpha]);
+            changed = new_color != *color;
+            *color = new_color;
+        }
+
+        // saturation to the right, value to the bottom
+        let hue = rgba_to_color({
+            let rgb = hsv_to_rgb(hsv[0], 1f32, 1f32);
+            [rgb[0], rgb[1], rgb[2], 1f32]
+        });
+        let white = make_color(255, 255, 255, 255);
+        let black = make_color(0, 0, 0, 255);
+        let clear = make_color(0, 0, 0, 0);
+        self.draw_list.add_rect_filled_multicolor(sv.min, sv.max, white, hue, hue, white);
+        self.draw_list.add_rect_filled_multicolor(sv.min, sv.max, clear, clear, black, black);
+
+        let marker = float2(sv.min.0 + hsv[1] * size, sv.min.1 + (1f32 - hsv[2]) * size);
+        self.draw_list.path()
+            .arc(marker, 4f32, 0f32, ::std::f32::consts::PI * 2f32, 12)
+            .stroke(1.5f32, true, if hsv[2] > 0.5f32 { black } else { white });
+
+        // hue bar, one gradient per sextant
+        let step = hue_bar.height() / 6f32;
+        for i in 0..6 {
+            let top = hsv_to_rgb(i as f32 / 6f32, 1f32, 1f32);
+            let bottom = hsv_to_rgb((i + 1) as f32 / 6f32, 1f32, 1f32);
+            let top = rgba_to_color([top[0], top[1], top[2], 1f32]);
+            let bottom = rgba_to_color([bottom[0], bottom[1], bottom[2], 1f32]);
+
+            let y = hue_bar.min.1 + i as f32 * step;
+            self.draw_list.add_rect_filled_multicolor(
+                float2(hue_bar.min.0, y),
+                float2(hue_bar.max.0, y + step),
+                top, top, bottom, bottom
+            );
+        }
+        self.color_bar_marker(hue_bar, hsv[0]);
+
+        // alpha bar over a checkerboard
+        self.draw_checkerboard(alpha_bar);
+        let opaque = with_alpha(*color, 255);
+        let transparent = with_alpha(*color, 0);
+        self.draw_list.add_rect_filled_multicolor(alpha_bar.min, alpha_bar.max, opaque, opaque, transparent, transparent);
+        self.color_bar_marker(alpha_bar, 1f32 - alpha);
+
+        changed
+    }
+
+    fn color_component(&mut self, bounds: Rect, prefix: &str, value: &mut f32) -> bool {
+        let io = &self.io;
+        let hovered = io.has_mouse_in_rect(bounds);
+        let dragging = io.is_mouse_down(MouseButton::Left) && io.has_mouse_click_in_rect(MouseButton::Left, bounds);
+
+        let mut changed = false;
+        if dragging && io.mouse_delta.0 != 0f32 {
+            let new_value = (*value + io.mouse_delta.0 / 255f32).max(0f32).min(1f32);
+            changed = new_value != *value;
+            *value = new_value;
+        }
+
+        if hovered || dragging {
+            self.cursor = CursorType::ResizeHorizontal;
+        }
+
+        let style = &self.style.textfield;
+        let (text, border, background) = if dragging {
+            (style.active_text, style.active_border, style.active)
+        } else if hovered {
+            (style.hover_text, style.hover_border, style.hover)
+        } else {
+            (style.normal_text, style.normal_border, style.normal)
+        };
+        self.draw_textfield_bg(bounds, border, background);
+        self.color_centered_text(&format!("{}:{}", prefix, (*value * 255f32).round()), bounds, text.color);
+
+        changed
+    }
+
+    fn color_centered_text(&mut self, text: &str, bounds: Rect, color: UColor) {
+        let y = bounds.min.1 + ((bounds.height() - self.default_font.advance_y()) * 0.5f32).round();
+
+        self.draw_list.push_clip_rect(bounds.to_float4());
+        self.draw_list.add_text_wrapped(
+            &mut *self.renderer,
+            &mut self.default_font,
+            text,
+            float2(bounds.min.0, y),
+            TextAlignment::Centered,
+            bounds.width(),
+            color
+        );
+        self.draw_list.pop_clip_rect();
+    }
+
+    fn color_bar_marker(&mut self, bar: Rect, t: f32) {
+        let y = (bar.min.1 + t * bar.height()).round();
+
+        self.draw_list.add_rect_filled(
+            float2(bar.min.0 - 2f32, y - 1f32),
+            float2(bar.max.0 + 2f32, y + 1f32),
+            0f32,
+            make_color(255, 255, 255, 255)
+        );
+    }
+
+    fn draw_checkerboard(&mut self, bounds: Rect) {
+        let cell = (bounds.width() * 0.5f32).max(1f32);
+        let light = make_color(204, 204, 204, 255);
+        let dark = make_color(128, 128, 128, 255);
+
+        self.draw_list.add_rect_filled(bounds.min, bounds.max, 0f32, light);
+
+        let mut row = 0;
+        let mut y = bounds.min.1;
+        while y < bounds.max.1 {
+            let mut x = bounds.min.0 + if row % 2 == 0 { 0f32 } else { cell };
+            while x < bounds.max.0 {
+                let max = float2((x + cell).min(bounds.max.0), (y + cell).min(bounds.max.1));
+                self.draw_list.add_rect_filled(float2(x, y), max, 0f32, dark);
+                x += cell * 2f32;
+            }
+
+            y += cell;
+            row += 1;
+        }
+    }
+
+    /// Color with a checkerboard under its translucent half.
+    fn draw_color_swatch(&mut self, bounds: Rect, color: UColor) {
+        let half = Rect::new(float2(bounds.center().0, bounds.min.1), bounds.max);
+
+        self.draw_checkerboard(half);
+        self.draw_list.add_rect_filled(bounds.min, float2(half.min.0, bounds.max.1), 0f32, with_alpha(color, 255));
+        self.draw_list.add_rect_filled(half.min, half.max, 0f32, color);
+        self.draw_list.add_rect(bounds.min, bounds.max, 0f32, 1f32, make_color(20, 20, 20, 255));
+    }
+}
@@ -0,0 +1,732 @@
+use crate::{
+    Context, MouseButton,
+    Background,
+    ButtonFlags,
+    CursorType,
+    Id,
+    RowType,
+    SettingsSection,
+    WindowFlags,
+    make_color,
+    parse_id,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+use super::{
+    WidgetState,
+};
+
+const DOCK_SPLITTER_SIZE: f32 = 4f32;
+const DOCK_MIN_SIZE: f32 = 32f32;
+const DOCK_TARGET_SIZE: f32 = 32f32;
+const DOCK_UNDOCK_DISTANCE: f32 = 12f32;
+/// Settings for node indices from this one on are ignored.
+const DOCK_MAX_NODES: usize = 1024;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DockSplit {
+    /// Children are placed side by side, left and right.
+    Horizontal,
+    /// Children are stacked, top and bottom.
+    Vertical,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DockTarget {
+    Center,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Clone)]
+pub struct DockNode {
+    pub alive: bool,
+    /// Id of the dock space when this is a root node, 0 otherwise.
+    pub space: Id,
+    pub parent: Option<usize>,
+    pub children: Option<[usize; 2]>,
+    pub split: DockSplit,
+    pub ratio: f32,
+    /// Windows docked as tabs in this node, in tab order.
+    pub windows: Vec<Id>,
+    pub selected: Id,
+    pub bounds: Rect,
+
+    host: Option<usize>,
+    last_frame: u32,
+}
+
+impl DockNode {
+    pub fn new() -> Self {
+        DockNode {
+            alive: true,
+            space: 0,
+            parent: None,
+            children: None,
+            split: DockSplit::Horizontal,
+            ratio: 0.5f32,
+            windows: Vec::new(),
+            selected: 0,
+            bounds: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
+
+            host: None,
+            last_frame: 0,
+        }
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_none()
+    }
+
+    /// True if the node's dock space was submitted this or last frame.
+    pub fn is_laid_out(&self, frame: u32) -> bool {
+        self.last_frame + 1 >= frame
+    }
+}
+
+fn split_rect(bounds: Rect, split: DockSplit, ratio: f32) -> (Rect, Rect, Rect) {
+    match split {
+        DockSplit::Horizontal => {
+            let total = (bounds.width() - DOCK_SPLITTER_SIZE).max(0f32);
+            let x = (bounds.min.0 + total * ratio).round();
+
+            (
+                Rect::new(bounds.min, float2(x, bounds.max.1)),
+                Rect::new(float2(x, bounds.min.1), float2(x + DOCK_SPLITTER_SIZE, bounds.max.1)),
+                Rect::new(float2(x + DOCK_SPLITTER_SIZE, bounds.min.1), bounds.max),
+            )
+        }
+        DockSplit::Vertical => {
+            let total = (bounds.height() - DOCK_SPLITTER_SIZE).max(0f32);
+            let y = (bounds.min.1 + total * ratio).round();
+
+            (
+                Rect::new(bounds.min, float2(bounds.max.0, y)),
+                Rect::new(float2(bounds.min.0, y), float2(bounds.max.0, y + DOCK_SPLITTER_SIZE)),
+                Rect::new(float2(bounds.min.0, y + DOCK_SPLITTER_SIZE), bounds.max),
+            )
+        }
+    }
+}
+
+/// Area a window would take up when dropped on `target` of a node.
+fn dock_target_preview(bounds: Rect, target: DockTarget) -> Rect {
+    let center = bounds.center();
+
+    match target {
+        DockTarget::Center => bounds,
+        DockTarget::Left => Rect::new(bounds.min, float2(center.0, bounds.max.1)),
+        DockTarget::Right => Rect::new(float2(center.0, bounds.min.1), bounds.max),
+        DockTarget::Top => Rect::new(bounds.min, float2(bounds.max.0, center.1)),
+        DockTarget::Bottom => Rect::new(float2(bounds.min.0, center.1), bounds.max),
+    }
+}
+
+fn dock_target_rects(bounds: Rect, has_content: bool) -> Vec<(DockTarget, Rect)> {
+    let center = bounds.center();
+    let half = float2(DOCK_TARGET_SIZE, DOCK_TARGET_SIZE) * 0.5f32;
+    let step = DOCK_TARGET_SIZE + 4f32;
+
+    let square = |offset: float2| {
+        let c = center + offset;
+        Rect::new(c - half, c + half)
+    };
+
+    let mut targets = vec![(DockTarget::Center, square(float2(0f32, 0f32)))];
+    if has_content {
+        targets.push((DockTarget::Left, square(float2(-step, 0f32))));
+        targets.push((DockTarget::Right, square(float2(step, 0f32))));
+        targets.push((DockTarget::Top, square(float2(0f32, -step))));
+        targets.push((DockTarget::Bottom, square(float2(0f32, step))));
+    }
+
+    targets
+}
+
+impl Context {
+    /// Area of a leaf node below its tab strip, where the selected window
+    /// is placed.
+    pub(crate) fn dock_content_bounds(&self, node: usize) -> Rect {
+        let mut bounds = self.dock_nodes[node].bounds;
+        bounds.min.1 = (bounds.min.1 + self.style.tab.tab_height()).min(bounds.max.1);
+
+        bounds
+    }
+
+    fn alloc_dock_node(&mut self) -> usize {
+        if let Some(idx) = self.dock_nodes.iter().position(|n| !n.alive) {
+            self.dock_nodes[idx] = DockNode::new();
+
+            idx
+        } else {
+            self.dock_nodes.push(DockNode::new());
+
+            self.dock_nodes.len() - 1
+        }
+    }
+
+    fn find_dock_space(&self, space: Id) -> Option<usize> {
+        self.dock_nodes.iter()
+            .position(|n| n.alive && n.space == space && n.parent.is_none())
+    }
+
+    /// Fills the rest of the current panel with a dock space that windows
+    /// can be dragged into by their header.
+    pub fn dock_space(&mut self, title: &str) {
+        let space = self.id(title);
+
+        let height = self.available_height();
+        self.row(RowType::dynamic_ex(1, height));
+        self.column(Some(1f32));
+        let (bounds, _state) = self.widget(None);
+
+        let root = if let Some(root) = self.find_dock_space(space) {
+            root
+        } else {
+            let root = self.alloc_dock_node();
+            self.dock_nodes[root].space = space;
+
+            root
+        };
+
+        self.dock_nodes[root].host = self.active;
+        self.dock_layout(root, bounds);
+        self.dock_tab_strips(root);
+
+        // the host may have been brought to the front, keep its docked
+        // windows on top of it
+        let docked: Vec<usize> = self.windows.iter().enumerate()
+            .filter(|&(_, w)| w.dock_node.map_or(false, |n| self.dock_root(n) == root))
+            .map(|(idx, _)| idx)
+            .collect();
+        for wnd_idx in docked {
+            self.dock_move_above_host(wnd_idx, root);
+        }
+    }
+
+    fn dock_root(&self, node: usize) -> usize {
+        let mut root = node;
+        while let Some(parent) = self.dock_nodes[root].parent {
+            root = parent;
+        }
+
+        root
+    }
+
+    fn dock_layout(&mut self, idx: usize, bounds: Rect) {
+        let frame = self.frame;
+        let node = &mut self.dock_nodes[idx];
+        node.bounds = bounds;
+        node.last_frame = frame;
+
+        let (children, split, ratio) = match node.children {
+            Some(children) => (children, node.split, node.ratio),
+            None => return,
+        };
+
+        let (_, splitter, _) = split_rect(bounds, split, ratio);
+        let total = match split {
+            DockSplit::Horizontal => bounds.width() - DOCK_SPLITTER_SIZE,
+            DockSplit::Vertical => bounds.height() - DOCK_SPLITTER_SIZE,
+        };
+
+        let io = &mut self.io;
+        let hovered = io.has_mouse_in_rect(splitter);
+        let dragging = io.is_mouse_down(MouseButton::Left) && io.has_mouse_click_in_rect(MouseButton::Left, splitter);
+        let mut ratio = ratio;
+
+        if dragging && total > 0f32 {
+            let delta = match split {
+                DockSplit::Horizontal => io.mouse_delta.0,
+                DockSplit::Vertical => io.mouse_delta.1,
+            };
+            let min = (DOCK_MIN_SIZE / total).min(0.5f32);
+            let new_ratio = ((ratio * total + delta) / total).max(min).min(1f32 - min);
+            let applied = (new_ratio - ratio) * total;
+
+            match split {
+                DockSplit::Horizontal => io.mouse_clicked_pos[MouseButton::Left as usize].0 += applied,
+                DockSplit::Vertical => io.mouse_clicked_pos[MouseButton::Left as usize].1 += applied,
+            }
+
+            ratio = new_ratio;
+            self.dock_nodes[idx].ratio = ratio;
+
+            if applied != 0f32 {
+                self.mark_settings_dirty();
+            }
+        }
+
+        if hovered || dragging {
+            self.cursor = match split {
+                DockSplit::Horizontal => CursorType::ResizeHorizontal,
+                DockSplit::Vertical => CursorType::ResizeVertical,
+            };
+        }
+
+        let (a, splitter, b) = split_rect(bounds, split, ratio);
+
+        let style = &self.style.button;
+        let background = if dragging {
+            style.active
+        } else if hovered {
+            style.hover
+        } else {
+            Background::Color(0)
+        };
+        if let Background::Color(col) = background {
+            self.draw_list.add_rect_filled(splitter.min, splitter.max, 0f32, col);
+        }
+
+        self.dock_layout(children[0], a);
+        self.dock_layout(children[1], b);
+    }
+
+    fn dock_tab_strips(&mut self, idx: usize) {
+        if let Some(children) = self.dock_nodes[idx].children {
+            self.dock_tab_strips(children[0]);
+            self.dock_tab_strips(children[1]);
+
+            return;
+        }
+
+        let bounds = self.dock_nodes[idx].bounds;
+        let tab_height = self.style.tab.tab_height();
+        let padding = self.style.tab.padding;
+        let windows = self.dock_nodes[idx].windows.clone();
+
+        self.draw_list.push_clip_rect(bounds.to_float4());
+
+        let mut offset = 0f32;
+        let mut undock = None;
+        for id in windows {
+            let wnd_idx = match self.find_window_hash(id) {
+                Some(wnd_idx) => wnd_idx,
+                None => continue,
+            };
+            if self.windows[wnd_idx].flags.intersects(WindowFlags::Closed | WindowFlags::Hidden) {
+                continue;
+            }
+
+            let name = self.windows[wnd_idx].name.clone();
+            let width = self.default_font.text_width(&mut *self.renderer, &name) + padding.0 * 2f32;
+            let origin = bounds.min + float2(offset, 0f32);
+            let tab = Rect::new(origin, origin + float2(width, tab_height));
+            offset += width + 2f32;
+
+            self.last_widget_state = WidgetState::None;
+            if self.button_behaviour(tab, ButtonFlags::PressOnClick) {
+                if self.dock_nodes[idx].selected != id {
+                    self.mark_settings_dirty();
+                }
+                self.dock_nodes[idx].selected = id;
+            }
+
+            let io = &self.io;
+            let pulled = io.is_mouse_down(MouseButton::Left)
+                && io.has_mouse_click_in_rect(MouseButton::Left, tab)
+                && (io.mouse - io.mouse_clicked_pos[MouseButton::Left as usize]).length() > DOCK_UNDOCK_DISTANCE;
+            if pulled {
+                undock = Some(wnd_idx);
+            }
+
+            let style = &self.style.tab;
+            let state = self.last_widget_state;
+            let (text, border, background) = if self.dock_nodes[idx].selected == id {
+                (style.active_text, style.active_border, style.active)
+            } else if state.contains(WidgetState::Hovering) {
+                (style.hover_text, style.hover_border, style.hover)
+            } else {
+                (style.normal_text, style.normal_border, style.normal)
+            };
+            self.tab_item_bg(tab, background, border);
+            self.tab_item_text(&name, tab, text);
+        }
+
+        self.draw_list.pop_clip_rect();
+
+        if let Some(wnd_idx) = undock {
+            self.undock_window(wnd_idx);
+
+            // keep dragging the now floating window by its header
+            let mouse = self.io.mouse;
+            let wnd = &mut self.windows[wnd_idx];
+            let size = wnd.bounds.max - wnd.bounds.min;
+            wnd.bounds.min = mouse - float2(20f32, 8f32);
+            wnd.bounds.max = wnd.bounds.min + size;
+            self.io.mouse_clicked_pos[MouseButton::Left as usize] = mouse;
+            self.move_window_to_front(wnd_idx);
+        }
+    }
+
+    /// Leaf node under the mouse that a window could be dropped into.
+    fn dock_hovered_node(&self) -> Option<usize> {
+        let mouse = self.io.mouse;
+
+        self.dock_nodes.iter().position(|n| {
+            n.alive && n.is_leaf() && n.is_laid_out(self.frame) && n.bounds.contains(mouse)
+        })
+    }
+
+    /// Shows drop targets while a floating window is dragged by its header
+    /// and docks it when released over one.
+    pub(crate) fn dock_window_drag(&mut self, wnd_idx: usize, dragging: bool) {
+        if !dragging && self.dock_drag != Some(wnd_idx) {
+            return;
+        }
+
+        let hovered = self.dock_hovered_node().map(|node| {
+            let has_content = !self.dock_nodes[node].windows.is_empty();
+            let bounds = if has_content {
+                self.dock_content_bounds(node)
+            } else {
+                self.dock_nodes[node].bounds
+            };
+
+            (node, bounds, dock_target_rects(bounds, has_content))
+        });
+
+        let mut target = None;
+        if let Some((node, bounds, targets)) = hovered {
+            for &(ty, rect) in &targets {
+                let active = self.io.has_mouse_in_rect(rect);
+                if active {
+                    target = Some((node, ty));
+
+                    let preview = dock_target_preview(bounds, ty);
+                    self.draw_list.add_rect_filled(preview.min, preview.max, 0f32, make_color(0, 122, 204, 60));
+                }
+
+                let col = if active {
+                    make_color(0, 122, 204, 230)
+                } else {
+                    make_color(0, 122, 204, 140)
+                };
+                self.draw_list.add_rect_filled(rect.min, rect.max, 2f32, col);
+            }
+        }
+
+        if dragging {
+            self.dock_drag = Some(wnd_idx);
+        } else {
+            if self.io.is_mouse_released(MouseButton::Left) {
+                if let Some((node, ty)) = target {
+                    self.dock_window(wnd_idx, node, ty);
+                }
+            }
+
+            self.dock_drag = None;
+        }
+    }
+
+    /// Docks a window into a node, either as a new tab or by splitting the
+    /// node in two.
+    pub fn dock_window(&mut self, wnd_idx: usize, node: usize, target: DockTarget) {
+        if self.windows[wnd_idx].dock_node.is_some() {
+            self.undock_window(wnd_idx);
+        }
+
+        let id = self.windows[wnd_idx].id;
+        let empty = self.dock_nodes[node].windows.is_empty() && self.dock_nodes[node].is_leaf();
+
+        let leaf = if target == DockTarget::Center || empty {
+            node
+        } else {
+            let first = self.alloc_dock_node();
+            let second = self.alloc_dock_node();
+
+            let parent = &mut self.dock_nodes[node];
+            let windows = ::std::mem::replace(&mut parent.windows, Vec::new());
+            let selected = parent.selected;
+            parent.selected = 0;
+            parent.ratio = 0.5f32;
+            parent.split = match target {
+                DockTarget::Left | DockTarget::Right => DockSplit::Horizontal,
+                _ => DockSplit::Vertical,
+            };
+            parent.children = Some([first, second]);
+
+            let (existing, new) = match target {
+                DockTarget::Left | DockTarget::Top => (second, first),
+                _ => (first, second),
+            };
+
+            self.dock_nodes[first].parent = Some(node);
+            self.dock_nodes[second].parent = Some(node);
+            self.dock_nodes[existing].windows = windows;
+            self.dock_nodes[existing].selected = selected;
+
+            for &docked in &self.dock_nodes[existing].windows {
+                if let Some(idx) = self.windows.iter().position(|w| w.id == docked) {
+                    self.windows[idx].dock_node = Some(existing);
+                }
+            }
+
+            new
+        };
+
+        let node = &mut self.dock_nodes[leaf];
+        node.windows.push(id);
+        node.selected = id;
+
+        let wnd = &mut self.windows[wnd_idx];
+        wnd.floating_bounds = wnd.bounds;
+        wnd.dock_node = Some(leaf);
+        wnd.flags.remove(WindowFlags::Minimized);
+
+        self.dock_move_above_host(wnd_idx, leaf);
+        self.mark_settings_dirty();
+    }
+
+    /// Places a docked window right above the window hosting its dock
+    /// space, so floating windows stay on top of it.
+    fn dock_move_above_host(&mut self, wnd_idx: usize, node: usize) {
+        let root = self.dock_root(node);
+        let host = match self.dock_nodes[root].host {
+            Some(host) => host,
+            None => return,
+        };
+
+        if let Some(pos) = self.window_stack.iter().position(|&w| w == wnd_idx) {
+            self.window_stack.remove(pos);
+        }
+
+        let host_pos = self.window_stack.iter().position(|&w| w == host)
+            .map(|p| p + 1)
+            .unwrap_or(0);
+        self.window_stack.insert(host_pos, wnd_idx);
+    }
+
+    pub fn undock_window(&mut self, wnd_idx: usize) {
+        let node = match self.windows[wnd_idx].dock_node.take() {
+            Some(node) => node,
+            None => return,
+        };
+
+        let id = self.windows[wnd_idx].id;
+        let floating = self.windows[wnd_idx].floating_bounds;
+        if floating.width() > 0f32 && floating.height() > 0f32 {
+            self.windows[wnd_idx].bounds = floating;
+        }
+
+        let dock = &mut self.dock_nodes[node];
+        dock.windows.retain(|&w| w != id);
+        if dock.selected == id {
+            dock.selected = dock.windows.first().cloned().unwrap_or(0);
+        }
+
+        if dock.windows.is_empty() {
+            self.collapse_dock_node(node);
+        }
+
+        self.mark_settings_dirty();
+    }
+
+    /// Removes an empty leaf by moving its sibling up into the parent.
+    fn collapse_dock_node(&mut self, node: usize) {
+        let parent = match self.dock_nodes[node].parent {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        let children = self.dock_nodes[parent].children.unwrap();
+        let sibling = if children[0] == node { children[1] } else { children[0] };
+        let sibling_node = self.dock_nodes[sibling].clone();
+
+        let p = &mut self.dock_nodes[parent];
+        p.children = sibling_node.children;
+        p.split = sibling_node.split;
+        p.ratio = sibling_node.ratio;
+        p.windows = sibling_node.windows;
+        p.selected = sibling_node.selected;
+
+        if let Some(grandchildren) = sibling_node.children {
+            self.dock_nodes[grandchildren[0]].parent = Some(parent);
+            self.dock_nodes[grandchildren[1]].parent = Some(parent);
+        }
+
+        for &docked in &self.dock_nodes[parent].windows {
+            if let Some(idx) = self.windows.iter().position(|w| w.id == docked) {
+                self.windows[idx].dock_node = Some(parent);
+            }
+        }
+
+        self.dock_nodes[node].alive = false;
+        self.dock_nodes[sibling].alive = false;
+    }
+
+    pub(crate) fn save_dock_settings(&self, out: &mut String) {
+        use std::fmt::Write;
+
+        for (idx, node) in self.dock_nodes.iter().enumerate() {
+            if !node.alive {
+                continue;
+            }
+
+            let _ = writeln!(out, "[DockNode][{}]", idx);
+            if node.space != 0 {
+                let _ = writeln!(out, "Space=0x{:08x}", node.space);
+            }
+            if let Some(parent) = node.parent {
+                let _ = writeln!(out, "Parent={}", parent);
+            }
+            if let Some(children) = node.children {
+                let split = match node.split {
+                    DockSplit::Horizontal => "H",
+                    DockSplit::Vertical => "V",
+                };
+                let _ = writeln!(out, "Split={}", split);
+                let _ = writeln!(out, "Ratio={}", node.ratio);
+                let _ = writeln!(out, "Children={},{}", children[0], children[1]);
+            }
+            if !node.windows.is_empty() {
+                let windows: Vec<String> = node.windows.iter()
+                    .map(|id| format!("0x{:08x}", id))
+                    .collect();
+                let _ = writeln!(out, "Windows={}", windows.join(","));
+                let _ = writeln!(out, "Selected=0x{:08x}", node.selected);
+            }
+            out.push('\n');
+        }
+    }
+
+    pub(crate) fn load_dock_settings(&mut self, section: &SettingsSection) {
+        let idx = match section.name.parse::<usize>() {
+            Ok(idx) if idx < DOCK_MAX_NODES => idx,
+            _ => return,
+        };
+
+        while self.dock_nodes.len() <= idx {
+            let mut dead = DockNode::new();
+            dead.alive = false;
+            self.dock_nodes.push(dead);
+        }
+
+        let mut node = DockNode::new();
+        for &(key, val) in &section.entries {
+            match key {
+                "Space" => node.space = parse_id(val).unwrap_or(0),
+                "Parent" => node.parent = val.parse().ok(),
+                "Split" => node.split = if val == "V" { DockSplit::Vertical } else { DockSplit::Horizontal },
+                "Ratio" => node.ratio = val.parse().unwrap_or(0.5f32),
+                "Children" => {
+                    let children: Vec<usize> = val.split(',').filter_map(|c| c.trim().parse().ok()).collect();
+                    if children.len() == 2 {
+                        node.children = Some([children[0], children[1]]);
+                    }
+                },
+                "Windows" => node.windows = val.split(',').filter_map(parse_id).collect(),
+                "Selected" => node.selected = parse_id(val).unwrap_or(0),
+                _ => {}
+            }
+        }
+
+        self.dock_nodes[idx] = node;
+    }
+
+    /// Points every window at the node it's docked in after loading, once
+    /// the loaded nodes have been checked.
+    pub(crate) fn dock_fixup_windows(&mut self) {
+        validate_dock_nodes(&mut self.dock_nodes);
+
+        let len = self.dock_nodes.len();
+        for idx in 0..len {
+            if !self.dock_nodes[idx].alive {
+                continue;
+            }
+
+            let windows = self.dock_nodes[idx].windows.clone();
+            for id in windows {
+                if let Some(wnd_idx) = self.find_window_hash(id) {
+                    self.windows[wnd_idx].dock_node = Some(idx);
+                }
+            }
+        }
+    }
+}
+
+/// Drops loaded nodes whose links don't form a tree: out of range or dead
+/// parents and children, children that don't point back at their parent,
+/// and cycles. Dropping a node breaks the links of its neighbours, so this
+/// repeats until nothing changes and a damaged tree goes away as a whole.
+fn validate_dock_nodes(nodes: &mut Vec<DockNode>) {
+    let len = nodes.len();
+    let alive = |nodes: &Vec<DockNode>, idx: usize| idx < len && nodes[idx].alive;
+
+    loop {
+        let mut bad = Vec::new();
+
+        for idx in 0..len {
+            if !nodes[idx].alive {
+                continue;
+            }
+            let node = &nodes[idx];
+
+            let parent_ok = match node.parent {
+                Some(parent) => parent != idx && alive(nodes, parent)
+                    && nodes[parent].children.map_or(false, |c| c.contains(&idx)),
+                None => true,
+            };
+            let children_ok = match node.children {
+                Some([a, b]) => a != b && a != idx && b != idx
+                    && alive(nodes, a) && alive(nodes, b)
+                    && nodes[a].parent == Some(idx) && nodes[b].parent == Some(idx),
+                None => true,
+            };
+
+            // a chain longer than the node count loops
+            let mut steps = 0;
+            let mut current = idx;
+            while let Some(parent) = nodes[current].parent {
+                if parent >= len || steps > len {
+                    break;
+                }
+                current = parent;
+                steps += 1;
+            }
+
+            if !parent_ok || !children_ok || steps > len {
+                bad.push(idx);
+            }
+        }
+
+        if bad.is_empty() {
+            break;
+        }
+
+        for idx in bad {
+            nodes[idx].alive = false;
+        }
+    }
+}
+
+#[test]
+fn dock_nodes_validation() {
+    let node = |parent: Option<usize>, children: Option<[usize; 2]>| {
+        let mut node = DockNode::new();
+        node.parent = parent;
+        node.children = children;
+        node
+    };
+    let alive = |nodes: &Vec<DockNode>| nodes.iter().map(|n| n.alive).collect::<Vec<_>>();
+
+    let mut nodes = vec![node(None, Some([1, 2])), node(Some(0), None), node(Some(0), None)];
+    validate_dock_nodes(&mut nodes);
+    assert_eq!(alive(&nodes), vec![true, true, true]);
+
+    // missing child takes the whole tree with it, the other root stays
+    let mut nodes = vec![node(None, Some([1, 7])), node(Some(0), None), node(None, None)];
+    validate_dock_nodes(&mut nodes);
+    assert_eq!(alive(&nodes), vec![false, false, true]);
+
+    // a node listing itself and a two node cycle
+    let mut nodes = vec![node(Some(0), Some([0, 0])), node(Some(2), Some([2, 3])), node(Some(1), Some([1, 3])), node(Some(1), None)];
+    validate_dock_nodes(&mut nodes);
+    assert_eq!(alive(&nodes), vec![false, false, false, false]);
+}
@@ -0,0 +1,57 @@
+use crate::{
+    Context,
+    ButtonFlags,
+    Image,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+use super::{
+    WidgetState,
+    WidgetLayoutState,
+};
+
+impl Context {
+    /// Draws `img` scaled to `size` at the start of the next widget slot.
+    pub fn image(&mut self, img: &Image, size: float2) {
+        let (bounds, state) = self.widget(Some(size.1));
+        if state == WidgetLayoutState::Hidden {
+            return;
+        }
+        let rect = Rect::new(bounds.min, bounds.min + size);
+
+        self.draw_list.add_image(img.handle(), rect, img.uv(), 0xffffffff);
+    }
+
+    /// Button showing `img` at its pixel size, centered in the button.
+    pub fn image_button(&mut self, img: &Image) -> bool {
+        self.last_widget_state = WidgetState::None;
+
+        let padding = self.style.button.padding;
+        let size = img.size();
+        let (bounds, state) = self.widget(Some(size.1 + padding.1 * 2f32));
+        if state == WidgetLayoutState::Hidden {
+            return false;
+        }
+        let pressed = self.button_behaviour(bounds, ButtonFlags::None);
+
+        let style = &self.style.button;
+        let state = self.last_widget_state;
+        let (border, background) = if state.contains(WidgetState::Active) {
+            (style.active_border, style.active)
+        } else if state.contains(WidgetState::Hovering) {
+            (style.hover_border, style.hover)
+        } else {
+            (style.normal_border, style.normal)
+        };
+        self.draw_button(bounds, border, background);
+
+        let min = (bounds.center() - size * 0.5f32).round();
+        self.draw_list.add_image(img.handle(), Rect::new(min, min + size), img.uv(), 0xffffffff);
+
+        pressed
+    }
+}
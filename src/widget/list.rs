@@ -0,0 +1,268 @@
+use crate::{
+    Context,
+    Background,
+    ButtonFlags,
+    PanelFlags,
+    RowType,
+};
+
+use super::{
+    WidgetState,
+    WidgetLayoutState,
+};
+
+use std::ops::Range;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum SelectionMode {
+    Single,
+    /// Ctrl toggles single items, Shift selects the range from the last
+    /// clicked item.
+    Multiple,
+}
+
+/// Selected indices of a list, kept sorted.
+pub struct ListSelection {
+    mode: SelectionMode,
+    selected: Vec<usize>,
+    anchor: Option<usize>,
+}
+
+impl ListSelection {
+    pub fn new(mode: SelectionMode) -> Self {
+        ListSelection {
+            mode,
+            selected: Vec::new(),
+            anchor: None,
+        }
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.binary_search(&index).is_ok()
+    }
+
+    pub fn selected(&self) -> &[usize] {
+        &self.selected
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    /// Replaces the selection with `index`.
+    pub fn select(&mut self, index: usize) {
+        self.selected.clear();
+        self.selected.push(index);
+        self.anchor = Some(index);
+    }
+
+    /// Applies a click on `index` with the given modifier keys held.
+    pub fn click(&mut self, index: usize, ctrl: bool, shift: bool) {
+        if self.mode == SelectionMode::Single || (!ctrl && !shift) {
+            self.select(index);
+            return;
+        }
+
+        if shift {
+            let anchor = self.anchor.unwrap_or(index);
+            let (first, last) = (anchor.min(index), anchor.max(index));
+
+            if !ctrl {
+                self.selected.clear();
+            }
+            self.selected.extend(first..=last);
+            self.selected.sort();
+            self.selected.dedup();
+            self.anchor = Some(anchor);
+        } else {
+            match self.selected.binary_search(&index) {
+                Ok(pos) => { self.selected.remove(pos); },
+                Err(pos) => self.selected.insert(pos, index),
+            }
+            self.anchor = Some(index);
+        }
+    }
+}
+
+/// Visible part of a list with rows of equal height. Only the rows in
+/// `range()` need to be submitted, the space of the others is skipped.
+pub struct ListClipper {
+    pub display_start: usize,
+    pub display_end: usize,
+    items_count: usize,
+    pitch: f32,
+    start_y: f32,
+}
+
+impl ListClipper {
+    pub fn range(&self) -> Range<usize> {
+        self.display_start..self.display_end
+    }
+}
+
+/// Rows of `count` items starting at `start_y` that overlap `min_y..max_y`.
+fn visible_range(start_y: f32, min_y: f32, max_y: f32, count: usize, pitch: f32) -> (usize, usize) {
+    if pitch <= 0f32 || max_y <= min_y {
+        return (0, count);
+    }
+
+    let first = ((min_y - start_y) / pitch).floor().max(0f32) as usize;
+    let last = ((max_y - start_y) / pitch).ceil().max(0f32) as usize;
+
+    (first.min(count), last.min(count))
+}
+
+impl Context {
+    pub fn selectable_height(&self) -> f32 {
+        self.default_font.height() + self.style.selectable.padding.1 * 2f32
+    }
+
+    /// Full width row that highlights when `selected`, returns true when
+    /// clicked.
+    pub fn selectable(&mut self, label: &str, selected: bool) -> bool {
+        let h = self.selectable_height();
+        let (bounds, state) = self.widget(Some(h));
+        if state == WidgetLayoutState::Hidden {
+            return false;
+        }
+
+        self.last_widget_state = WidgetState::None;
+        let pressed = self.button_behaviour(bounds, ButtonFlags::None);
+
+        let style = &self.style.selectable;
+        let state = self.last_widget_state;
+        let (text, background) = if state.contains(WidgetState::Active) {
+            (style.hover_text, style.active)
+        } else if selected {
+            (style.selected_text, style.selected)
+        } else if state.contains(WidgetState::Hovering) {
+            (style.hover_text, style.hover)
+        } else {
+            (style.normal_text, style.normal)
+        };
+        let padding = style.padding;
+
+        match background {
+            Background::Color(col) => {
+                self.draw_list.add_rect_filled(bounds.min, bounds.max, 0f32, col);
+            },
+            Background::NineSlice(image) => {
+                self.draw_list.add_nine_slice(&image, bounds, 0xffffffff);
+            },
+            _ => {}
+        }
+
+        let inner = bounds.pad_sides(padding.0, padding.1, padding.0, padding.1);
+        self.draw_list.push_clip_rect(bounds.to_float4());
+        self.draw_list.add_text_wrapped(
+            &mut *self.renderer,
+            &mut self.default_font,
+            label,
+            inner.min,
+            text.align,
+            inner.width(),
+            text.color
+        );
+        self.draw_list.pop_clip_rect();
+
+        pressed
+    }
+
+    /// Scrolling panel of `height` with one item per row, close with
+    /// `end_list_box`.
+    pub fn begin_list_box(&mut self, label: &str, height: f32) -> bool {
+        let (bounds, _state) = self.widget(Some(height));
+
+        let style = &self.style.selectable;
+        let (border, background) = (style.list_border, style.list_background);
+        self.draw_button(bounds, border, background);
+
+        if !self.begin_panel_ex(label, Some(bounds), None, PanelFlags::None) {
+            return false;
+        }
+
+        self.row(RowType::dynamic(1));
+        self.column(Some(1f32));
+
+        true
+    }
+
+    pub fn end_list_box(&mut self) {
+        self.end_panel();
+    }
+
+    /// Selectable that updates `selection` from clicks and the held
+    /// modifier keys.
+    pub fn list_box_item(&mut self, label: &str, index: usize, selection: &mut ListSelection) -> bool {
+        let selected = selection.is_selected(index);
+
+        if self.selectable(label, selected) {
+            selection.click(index, self.io.key_ctrl, self.io.key_shift);
+            return true;
+        }
+
+        false
+    }
+
+    /// Starts a list of `items_count` rows of `item_height` on a new row and
+    /// skips the rows above the visible area of the panel.
+    pub fn begin_list_clipper(&mut self, items_count: usize, item_height: f32) -> ListClipper {
+        // rows are separated by the window spacing
+        let pitch = item_height + self.style.window.spacing.1;
+        let start_y = self.panel_skip(0f32);
+
+        let panel = &self.panel_stack[self.current_panel];
+        let clip = self.draw_list.current_clip_rect();
+        let min_y = panel.bounds.min.1.max(clip.1);
+        let max_y = panel.bounds.max.1.min(clip.3);
+        let (display_start, display_end) = visible_range(start_y, min_y, max_y, items_count, pitch);
+
+        self.panel_skip(display_start as f32 * pitch);
+
+        ListClipper {
+            display_start,
+            display_end,
+            items_count,
+            pitch,
+            start_y,
+        }
+    }
+
+    /// Skips the rows below the visible area so the panel keeps the full
+    /// height of the list for scrolling.
+    pub fn end_list_clipper(&mut self, clipper: ListClipper) {
+        let y = self.panel_skip(0f32);
+        let end_y = clipper.start_y + clipper.items_count as f32 * clipper.pitch;
+
+        self.panel_skip((end_y - y).max(0f32));
+    }
+}
+
+#[test]
+fn list_selection() {
+    let mut selection = ListSelection::new(SelectionMode::Multiple);
+
+    selection.click(3, false, false);
+    selection.click(6, false, true);
+    assert_eq!(selection.selected(), &[3, 4, 5, 6]);
+
+    selection.click(4, true, false);
+    assert_eq!(selection.selected(), &[3, 5, 6]);
+
+    // range from the ctrl clicked item, added to the selection
+    selection.click(1, true, true);
+    assert_eq!(selection.selected(), &[1, 2, 3, 4, 5, 6]);
+
+    selection.click(9, false, false);
+    assert_eq!(selection.selected(), &[9]);
+
+    let mut single = ListSelection::new(SelectionMode::Single);
+    single.click(2, false, false);
+    single.click(5, true, true);
+    assert_eq!(single.selected(), &[5]);
+
+    assert_eq!(visible_range(0f32, 100f32, 200f32, 10000, 20f32), (5, 10));
+    assert_eq!(visible_range(-30f32, 0f32, 50f32, 10000, 20f32), (1, 4));
+    assert_eq!(visible_range(0f32, 0f32, 100f32, 3, 20f32), (0, 3));
+}
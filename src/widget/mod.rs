@@ -3,6 +3,7 @@ use crate::{
     Context,
     
     draw::{
+        OVERLAY_LAYER,
         TextureHandle,
     },
     math::{
@@ -18,6 +19,15 @@ mod textfield;
 mod tab;
 mod panel;
 mod slider;
+mod scrollbar;
+mod dock;
+mod splitter;
+mod image;
+mod plot;
+mod progress;
+mod color;
+mod list;
+mod table;
 
 pub use self::button::*;
 pub use self::window::*;
@@ -25,6 +35,15 @@ pub use self::text::*;
 pub use self::textfield::*;
 pub use self::tab::*;
 pub use self::panel::*;
+pub use self::scrollbar::*;
+pub use self::dock::*;
+pub use self::splitter::*;
+pub use self::image::*;
+pub use self::plot::*;
+pub use self::progress::*;
+pub use self::color::*;
+pub use self::list::*;
+pub use self::table::*;
 
 bitflags! {
     pub struct WidgetState: u32 {
@@ -41,12 +60,34 @@ bitflags! {
     }
 }
 
+/// How much of a widget is inside the current clip rect. Widgets still
+/// take their space when `Hidden` but don't need to draw anything.
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum WidgetLayoutState {
     Hidden,
     Visible,
     PartiallyVisible,
 }
 
+impl WidgetLayoutState {
+    /// Bounds touching the clip rect count as visible, so widgets of zero
+    /// width or height inside it aren't hidden.
+    pub fn classify(clip: Rect, bounds: Rect) -> Self {
+        let outside = bounds.max.0 < clip.min.0 || bounds.min.0 > clip.max.0
+            || bounds.max.1 < clip.min.1 || bounds.min.1 > clip.max.1;
+        let inside = bounds.min.0 >= clip.min.0 && bounds.min.1 >= clip.min.1
+            && bounds.max.0 <= clip.max.0 && bounds.max.1 <= clip.max.1;
+
+        if outside {
+            WidgetLayoutState::Hidden
+        } else if inside {
+            WidgetLayoutState::Visible
+        } else {
+            WidgetLayoutState::PartiallyVisible
+        }
+    }
+}
+
 impl Context {
     pub fn peek_widget_width(&mut self) -> f32 {
         self.panel_peek_width()
@@ -55,12 +96,71 @@ impl Context {
     pub fn widget(&mut self, height: Option<f32>) -> (Rect, WidgetLayoutState) {
         let bounds = self.panel_alloc_space(height, true);
         
-        (bounds, WidgetLayoutState::Visible)
+        (bounds, self.widget_layout_state(bounds))
     }
 
     pub fn widget_no_style(&mut self, height: Option<f32>) -> (Rect, WidgetLayoutState) {
         let bounds = self.panel_alloc_space(height, false);
         
-        (bounds, WidgetLayoutState::Visible)
+        (bounds, self.widget_layout_state(bounds))
+    }
+
+    /// Records the width the content of the next widget needs when the
+    /// layout sizes columns to it, `width` is only called then.
+    pub(crate) fn widget_content_width<F: FnOnce(&mut Self) -> f32>(&mut self, width: F) {
+        if self.panel_stack[self.current_panel].row.measure_width {
+            let width = width(self);
+            self.panel_stack[self.current_panel].row.item_content_width = width;
+        }
+    }
+
+    /// True when the next widget starts below the clip rect and is hidden
+    /// whatever its height, widgets can skip measuring their content then.
+    /// Free rows can place widgets anywhere, they are never skipped.
+    pub(crate) fn widget_below_clip(&self) -> bool {
+        let panel = &self.panel_stack[self.current_panel];
+        let free = match panel.row.ty {
+            RowLayoutType::DynamicFree | RowLayoutType::StaticFree => true,
+            _ => false,
+        };
+
+        !free && panel.cursor.1 > self.draw_list.current_clip_rect().3
+    }
+
+    /// Classifies `bounds` against the clip rect widgets are drawn with.
+    pub fn widget_layout_state(&self, bounds: Rect) -> WidgetLayoutState {
+        let clip = self.draw_list.current_clip_rect();
+        let clip = Rect::new(float2(clip.0, clip.1), float2(clip.2, clip.3));
+
+        WidgetLayoutState::classify(clip, bounds)
+    }
+
+    /// Everything drawn until `end_overlay` goes on top of all windows and
+    /// is not clipped.
+    pub(crate) fn begin_overlay(&mut self) {
+        if let Some(idx) = self.active {
+            self.draw_list.push_layer(idx as u32);
+        }
+        self.draw_list.push_full_clip_rect();
+    }
+
+    pub(crate) fn end_overlay(&mut self) {
+        self.draw_list.pop_clip_rect();
+        self.draw_list.push_layer(OVERLAY_LAYER);
     }
 }
+
+#[test]
+fn widget_layout_states() {
+    let clip = Rect::new(float2(0f32, 0f32), float2(100f32, 100f32));
+    let at = |y: f32| Rect::new(float2(10f32, y), float2(90f32, y + 20f32));
+
+    assert_eq!(WidgetLayoutState::classify(clip, at(10f32)), WidgetLayoutState::Visible);
+    assert_eq!(WidgetLayoutState::classify(clip, at(90f32)), WidgetLayoutState::PartiallyVisible);
+    assert_eq!(WidgetLayoutState::classify(clip, at(-15f32)), WidgetLayoutState::PartiallyVisible);
+    assert_eq!(WidgetLayoutState::classify(clip, at(101f32)), WidgetLayoutState::Hidden);
+    assert_eq!(WidgetLayoutState::classify(clip, at(-40f32)), WidgetLayoutState::Hidden);
+
+    let empty = Rect::new(float2(10f32, 50f32), float2(90f32, 50f32));
+    assert_eq!(WidgetLayoutState::classify(clip, empty), WidgetLayoutState::Visible);
+}
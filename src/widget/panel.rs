@@ -1,6 +1,7 @@
 use crate::{
     Context, Window, WindowStyle, WindowFlags, MouseButton, TextAlignment,
-    WidgetState, Background, Border, CursorType,
+    WidgetState, Background, Border, CursorType, Id,
+    RowTemplateColumn,
     math::{*},
 };
 
@@ -12,9 +13,12 @@ bitflags! {
         const Background = 1 << 1;
         const Border = 1 << 2;
         const Styled = Self::Background.bits | Self::Border.bits;
+        const NoScrollbar = 1 << 3;
+        const ScrollAutoHide = 1 << 4;
     }
 }
 
+#[derive(Copy, Clone)]
 pub enum RowLayoutType {
     DynamicFixed,
     DynamicRow,
@@ -26,11 +30,17 @@ pub enum RowLayoutType {
     Static,
 }
 
+#[derive(Clone)]
 pub struct RowLayout {
     pub ty: RowLayoutType,
     pub index: u32,
     pub height: f32,
     pub max_height: f32,
+    /// Height of widgets that don't ask for one.
+    pub default_height: f32,
+    /// The row was started without a height and grows to its tallest
+    /// widget.
+    pub auto_height: bool,
 
     pub max_x: f32,
 
@@ -40,6 +50,21 @@ pub struct RowLayout {
     pub item_height: f32,
     pub item_offset: f32,
     pub filled: f32,
+    /// Right edge of the last widget placed in the row.
+    pub item_max_x: f32,
+    /// Widgets report the width of their content in `item_content_width`,
+    /// for layouts that size columns to it.
+    pub measure_width: bool,
+    pub item_content_width: f32,
+    /// Column ratios of `Dynamic` rows, pixel widths of `Static` ones.
+    pub ratios: Vec<f32>,
+    /// Columns of a row template until `row_template_end` solves them.
+    pub templates: Vec<RowTemplateColumn>,
+    /// Rect of the next widget in free rows, in ratios or pixels relative
+    /// to the row.
+    pub item: Rect,
+    /// Keep the next widget on this row even if it is full.
+    pub same_line: bool,
 }
 
 impl RowLayout {
@@ -49,6 +74,8 @@ impl RowLayout {
             index: 0,
             height: 0f32,
             max_height: 0f32,
+            default_height: 0f32,
+            auto_height: true,
             max_x: 0f32,
             ratio: 0f32,
             columns: 0,
@@ -56,6 +83,13 @@ impl RowLayout {
             item_height: 0f32,
             item_offset: 0f32,
             filled: 0f32,
+            item_max_x: 0f32,
+            measure_width: false,
+            item_content_width: 0f32,
+            ratios: Vec::new(),
+            templates: Vec::new(),
+            item: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
+            same_line: false,
         }
     }
 }
@@ -71,6 +105,7 @@ pub enum PanelType {
 }
 
 pub struct Panel {
+    pub id: Id,
     pub ty: PanelType,
     pub bounds: Rect,
     pub row: RowLayout,
@@ -80,15 +115,21 @@ pub struct Panel {
     pub header_height: f32,
     pub footer_height: f32,
     pub max_x: f32,
+    pub max_y: f32,
+    pub indent: f32,
     pub has_scrolling: bool,
     
     pub clip: Rect,
     pub offset: float2,
+    pub scrollbar: float2,
+    /// Row and content size to go back to after an anchored widget.
+    pub anchored: Option<(RowLayout, float2)>,
 }
 
 impl Panel {
     pub fn new() -> Self {
         Panel {
+            id: 0,
             ty: PanelType::Window,
             bounds: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
             row: RowLayout::new(),
@@ -97,9 +138,13 @@ impl Panel {
             header_height: 0f32,
             footer_height: 0f32,
             max_x: 0f32,
+            max_y: 0f32,
+            indent: 0f32,
             has_scrolling: true,
             clip: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
             offset: float2(0f32, 0f32),
+            scrollbar: float2(0f32, 0f32),
+            anchored: None,
         }
     }
     
@@ -127,16 +172,43 @@ impl Panel {
         let panel_padding = 2f32 * padding.0;
         let panel_spacing = columns as f32 * spacing.0;
 
-        self.bounds.width() - panel_padding - panel_spacing
+        self.bounds.width() - panel_padding - panel_spacing - self.indent
+    }
+
+    /// Goes back to the row and content size from before `anchored`, if
+    /// it was called.
+    pub fn end_anchored(&mut self) {
+        if let Some((row, max)) = self.anchored.take() {
+            self.row = row;
+            self.max_x = max.0;
+            self.max_y = max.1;
+        }
     }
 
     pub fn reset(&mut self) {
         self.row = RowLayout::new();
         self.cursor = float2(0f32, 0f32);
+        self.max_x = 0f32;
+        self.max_y = 0f32;
+        self.indent = 0f32;
         self.clip = Rect::new(float2(0f32, 0f32), float2(0f32, 0f32));
+        self.offset = float2(0f32, 0f32);
+        self.scrollbar = float2(0f32, 0f32);
+        self.anchored = None;
     }
 }
 
+/// What `end_panel` restores in the parent of a panel.
+#[derive(Clone)]
+pub struct PanelSave {
+    parent: Option<usize>,
+    row: RowLayout,
+    cursor: float2,
+    /// Bounds given to the panel, only set when they came out of the
+    /// parent's layout and should advance it.
+    outer: Option<Rect>,
+}
+
 impl Context {
     pub fn panel_new_row(&mut self, use_style: bool) {
         let idx = self.current_panel;
@@ -147,12 +219,15 @@ impl Context {
         } else {
             0f32
         };
-        let height = panel.row.max_height;
-        let row_height = height - spacing;
+        let row_height = if panel.row.auto_height {
+            None
+        } else {
+            Some(panel.row.default_height - spacing)
+        };
 
         let columns = panel.row.columns;
         
-        self.panel_layout(Some(row_height), columns, use_style);
+        self.panel_layout(row_height, columns, use_style);
     }
 
     pub fn panel_cursor(&self) -> float2 {
@@ -166,13 +241,17 @@ impl Context {
         let idx = self.current_panel;
         let panel = &mut self.panel_stack[idx];
         
-        panel.bounds.height() - panel.cursor.1
+        panel.bounds.max.1 - panel.cursor.1
     }
 
     pub fn panel_layout(&mut self, height: Option<f32>, columns: u32, use_style: bool) {
+        let min_height = self.default_font.height() + self.style.window.min_row_height_padding * 2f32;
+
         let idx = self.current_panel;
         let panel = &mut self.panel_stack[idx];
-        
+        // an anchored widget that never came doesn't change the new row
+        panel.end_anchored();
+
         let item_spacing = if use_style {
             self.style.window.spacing.1
         } else {
@@ -184,35 +263,66 @@ impl Context {
         panel.row.max_height = 0f32;
         panel.row.columns = columns;
         panel.row.item_offset = 0f32;
+        panel.row.filled = 0f32;
+        panel.row.same_line = false;
 
         if let Some(height) = height {
             panel.row.height = height + item_spacing;
+            panel.row.auto_height = false;
         } else {
-            // panel.row.height = panel.row.min_height + item_spacing.1;
+            panel.row.height = min_height + item_spacing;
+            panel.row.auto_height = true;
         }
+        panel.row.default_height = panel.row.height;
     }
     
     pub fn panel_alloc_space(&mut self, height: Option<f32>, use_style: bool) -> Rect {
         let idx = self.current_panel;
-        let (column, max_columns) = {
+        let (column, max_columns, same_line) = {
             let panel = &mut self.panel_stack[idx];
 
-            (panel.row.index, panel.row.columns)
+            (panel.row.index, panel.row.columns, panel.row.same_line)
         };
-        
-        if column >= max_columns {
+
+        let same_line = same_line && column > 0;
+        let row_full = column >= max_columns;
+        if row_full && !same_line {
             self.panel_new_row(use_style);
         }
 
         let panel = &mut self.panel_stack[idx];
-        if let Some(height) = height {
+        let free = match panel.row.ty {
+            RowLayoutType::DynamicFree | RowLayoutType::StaticFree => true,
+            _ => false,
+        };
+        // free rows keep the height they were started with, in the others
+        // the tallest widget decides where the next row starts
+        let height = height.unwrap_or(panel.row.default_height);
+        panel.row.item_height = height;
+        if !free {
             panel.row.max_height = panel.row.max_height.max(height);
             panel.row.height = height;
         }
-        let bounds = self.layout_widget_space(true, use_style);
+        if same_line && row_full {
+            // take the width of the last column
+            panel.row.index = max_columns - 1;
+        }
+        let mut bounds = self.layout_widget_space(true, use_style);
         
         let panel = &mut self.panel_stack[idx];
+        if same_line {
+            let spacing = if use_style { self.style.window.spacing.0 } else { 0f32 };
+            let offset = float2(panel.row.item_max_x + spacing - bounds.min.0, 0f32);
+            bounds = Rect::new(bounds.min + offset, bounds.max + offset);
+            panel.row.max_x = panel.row.max_x.max(bounds.max.0);
+        }
         panel.row.index += 1;
+        panel.row.item_max_x = bounds.max.0;
+        panel.row.same_line = false;
+        panel.max_x = panel.max_x.max(bounds.max.0);
+        panel.max_y = panel.max_y.max(bounds.max.1);
+
+        panel.end_anchored();
 
         bounds
     }
@@ -256,8 +366,8 @@ impl Context {
     }
 
     pub fn panel_end(&mut self) {
+        self.panel_scrollbars();
 
-        // TODO: scrollbars
         // TODO: panel border
         // TODO: draw resize handle
     }
@@ -284,27 +394,78 @@ impl Context {
             let (bounds, _state) = self.widget(None);
             bounds
         };
+
+        self.panel_push(title, bounds, tab_area, flags, true)
+    }
+
+    /// Pushes a panel over `bounds`, `nested` panels advance the layout of
+    /// the current panel when they end. Windows aren't nested, they only
+    /// get their parent back.
+    pub(crate) fn panel_push(&mut self, title: &str, bounds: Rect, tab_area: Option<(f32, f32)>, flags: PanelFlags, nested: bool) -> bool {
+        let save = if self.panel_saves.is_empty() {
+            PanelSave {
+                parent: None,
+                row: RowLayout::new(),
+                cursor: float2(0f32, 0f32),
+                outer: None,
+            }
+        } else {
+            let parent = &self.panel_stack[self.current_panel];
+            PanelSave {
+                parent: Some(self.current_panel),
+                row: parent.row.clone(),
+                cursor: parent.cursor,
+                outer: if nested { Some(bounds) } else { None },
+            }
+        };
+        self.panel_saves.push(save);
+        
+        let id = self.id(title);
+        let scroll_idx = self.scroll_states.get(id);
+        let scroll = self.scroll_states[scroll_idx];
+
+        let mut scroll_flags = WindowFlags::None;
+        if flags.contains(PanelFlags::NoScrollbar) {
+            scroll_flags |= WindowFlags::NoScrollbar;
+        }
+        if flags.contains(PanelFlags::ScrollAutoHide) {
+            scroll_flags |= WindowFlags::ScrollAutoHide;
+        }
         
         let idx = self.panel_index();
-        let panel = &mut self.panel_stack[idx];
-        panel.reset();
+        let (bmin, bmax) = {
+            let panel = &mut self.panel_stack[idx];
+            panel.reset();
+            panel.id = id;
+            panel.flags = scroll_flags;
 
-        let panel_padding = if let None = tab_area {
-            panel.get_padding(&self.style.window)
+            let panel_padding = if let None = tab_area {
+                panel.get_padding(&self.style.window)
+            } else {
+                float2(0f32, -1f32)
+            };
+            panel.bounds = bounds.grow(
+                float2(panel_padding.0, panel_padding.1),
+                float2(panel_padding.0, panel_padding.1)
+            );
+            panel.clip = panel.bounds;
+
+            (panel.bounds.min.round(), panel.bounds.max.round())
+        };
+        let scrollbar = self.panel_scrollbar_size(Rect::new(bmin, bmax), scroll.content_size, scroll_flags);
+
+        let panel = &mut self.panel_stack[idx];
+        panel.scrollbar = scrollbar;
+        panel.bounds.max -= scrollbar;
+        panel.offset = if scroll_flags.contains(WindowFlags::NoScrollbar) {
+            float2(0f32, 0f32)
         } else {
-            float2(0f32, -1f32)
+            scroll.offset
         };
-        panel.bounds = bounds.grow(
-            float2(panel_padding.0, panel_padding.1),
-            float2(panel_padding.0, panel_padding.1)
-        );
-        panel.cursor = panel.bounds.min;
+        panel.cursor = panel.bounds.min - panel.offset;
 
         self.current_panel = idx;
         self.panel_index += 1;
-
-        let bmin = panel.bounds.min.round();
-        let bmax = panel.bounds.max.round();
         if flags.contains(PanelFlags::Background) {
             let col = crate::style::make_color(60, 59, 64, 255);
             self.draw_list.add_rect_filled(bmin, bmax, 0f32, col);
@@ -330,14 +491,72 @@ impl Context {
                 self.draw_list.add_rect_gradient(border_bounds.min - float2(0.5f32,0.5f32), border_bounds.max, border.rounding, border.thickness, border.color & 0x00ffffff, border.color);
             }
         }
+
+        self.draw_list.push_clip_rect(Rect::new(bmin, bmax).to_float4());
         
         true
     }
     
+    /// Number of `end_panel` calls without a matching `begin_panel` and of
+    /// panels left open at the end of a frame so far. Stays 0 in correct
+    /// code, debug builds also assert.
+    pub fn panel_mismatches(&self) -> u32 {
+        self.panel_mismatches
+    }
+
+    /// Closes the current panel and gives its parent back its row and
+    /// cursor, moved past the space the panel took.
     pub fn end_panel(&mut self) {
-        self.panel_index -= 1;
-        if self.current_panel > 0 {
-            self.current_panel -= 1;
+        let save = match self.panel_saves.pop() {
+            Some(save) => save,
+            None => {
+                self.panel_mismatches += 1;
+                debug_assert!(false, "end_panel called without a matching begin_panel");
+                return;
+            }
+        };
+        self.panel_stack[self.current_panel].end_anchored();
+
+        self.panel_end();
+        self.draw_list.pop_clip_rect();
+        self.panel_index = self.panel_saves.len();
+
+        let parent = match save.parent {
+            Some(parent) => parent,
+            None => return,
+        };
+        self.current_panel = parent;
+
+        let spacing = self.style.window.spacing.1;
+        let panel = &mut self.panel_stack[parent];
+        panel.row = save.row;
+        panel.cursor = save.cursor;
+
+        if let Some(outer) = save.outer {
+            let free = match panel.row.ty {
+                RowLayoutType::DynamicFree | RowLayoutType::StaticFree => true,
+                _ => false,
+            };
+            let consumed = outer.max.1 - panel.cursor.1 + spacing;
+            if !free && outer.min.1 >= panel.cursor.1 {
+                panel.row.max_height = panel.row.max_height.max(consumed);
+            }
+            panel.max_x = panel.max_x.max(outer.max.0);
+            panel.max_y = panel.max_y.max(outer.max.1);
         }
     }
+
+    /// Reports panels left open at the end of the frame and drops them, so
+    /// the next frame starts from an empty stack.
+    pub(crate) fn panel_end_frame(&mut self) {
+        self.panel_mismatches += self.panel_saves.len() as u32;
+        debug_assert!(
+            self.panel_saves.is_empty(),
+            "{} panel(s) not closed with end_panel", self.panel_saves.len()
+        );
+
+        self.panel_saves.clear();
+        self.panel_index = 0;
+        self.current_panel = 0;
+    }
 }
@@ -0,0 +1,179 @@
+use crate::{
+    Context,
+    TextAlignment,
+    WidgetLayoutState,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum PlotType {
+    Lines,
+    Histogram,
+}
+
+impl Context {
+    /// Draws horizontal reference lines at `values` in the next plot.
+    pub fn set_next_plot_reference_lines(&mut self, values: &[f32]) {
+        self.next_plot_references.clear();
+        self.next_plot_references.extend_from_slice(values);
+    }
+
+    /// Line plot of `values`. `None` scales are taken from the values, a
+    /// width of 0 fills the column. `label` is drawn on top of the plot.
+    pub fn plot_lines(&mut self, label: &str, values: &[f32], scale_min: Option<f32>, scale_max: Option<f32>, size: float2) {
+        self.plot(PlotType::Lines, label, values, scale_min, scale_max, size);
+    }
+
+    pub fn plot_histogram(&mut self, label: &str, values: &[f32], scale_min: Option<f32>, scale_max: Option<f32>, size: float2) {
+        self.plot(PlotType::Histogram, label, values, scale_min, scale_max, size);
+    }
+
+    fn plot(&mut self, ty: PlotType, label: &str, values: &[f32], scale_min: Option<f32>, scale_max: Option<f32>, size: float2) {
+        let references = ::std::mem::replace(&mut self.next_plot_references, Vec::new());
+
+        let (bounds, state) = self.widget(Some(size.1));
+        if state == WidgetLayoutState::Hidden {
+            return;
+        }
+        let mut frame = bounds;
+        if size.0 > 0f32 {
+            frame.max.0 = frame.min.0 + size.0.min(bounds.width());
+        }
+
+        let style = &self.style.plot;
+        let padding = style.padding;
+        let (border, background) = (style.border, style.background);
+        self.draw_button(frame, border, background);
+
+        let inner = frame.pad_sides(padding.0, padding.1, padding.0, padding.1);
+        if inner.width() <= 0f32 || inner.height() <= 0f32 {
+            return;
+        }
+
+        let mut min = scale_min.unwrap_or(::std::f32::MAX);
+        let mut max = scale_max.unwrap_or(::std::f32::MIN);
+        if scale_min.is_none() || scale_max.is_none() {
+            for &v in values {
+                if scale_min.is_none() { min = min.min(v); }
+                if scale_max.is_none() { max = max.max(v); }
+            }
+        }
+        if values.is_empty() && (scale_min.is_none() || scale_max.is_none()) {
+            min = 0f32;
+            max = 1f32;
+        }
+        if max <= min {
+            max = min + 1f32;
+        }
+
+        let to_y = |v: f32| {
+            let t = ((v - min) / (max - min)).max(0f32).min(1f32);
+            inner.max.1 - t * inner.height()
+        };
+
+        let count = values.len();
+        let hovered = if count > 0 && self.io.has_mouse_in_rect(inner) {
+            let t = (self.io.mouse.0 - inner.min.0) / inner.width();
+            let idx = match ty {
+                PlotType::Lines => (t * (count - 1) as f32).round() as usize,
+                PlotType::Histogram => (t * count as f32) as usize,
+            };
+
+            Some(idx.min(count - 1))
+        } else {
+            None
+        };
+
+        self.draw_list.push_clip_rect(inner.to_float4());
+
+        let style = &self.style.plot;
+        match ty {
+            PlotType::Lines => {
+                let step = if count > 1 { inner.width() / (count - 1) as f32 } else { 0f32 };
+                let point = |i: usize| float2(inner.min.0 + i as f32 * step, to_y(values[i]));
+
+                if count > 1 {
+                    let mut path = self.draw_list.path();
+                    for i in 0..count {
+                        path = path.line(point(i));
+                    }
+                    path.stroke(1f32, false, style.line);
+                }
+
+                if let Some(idx) = hovered {
+                    let p = point(idx);
+                    let half = float2(2f32, 2f32);
+                    self.draw_list.add_rect_filled(p - half, p + half, 0f32, style.line_hover);
+                }
+            }
+            PlotType::Histogram => {
+                let bar_width = inner.width() / count.max(1) as f32;
+                let gap = if bar_width > 3f32 { 1f32 } else { 0f32 };
+                let base = to_y(0f32.max(min).min(max));
+
+                for (i, &v) in values.iter().enumerate() {
+                    let x = inner.min.0 + i as f32 * bar_width;
+                    let y = to_y(v);
+                    let color = if hovered == Some(i) { style.histogram_hover } else { style.histogram };
+
+                    self.draw_list.add_rect_filled(
+                        float2(x, y.min(base)),
+                        float2(x + bar_width - gap, y.max(base)),
+                        0f32,
+                        color
+                    );
+                }
+            }
+        }
+
+        let reference = style.reference;
+        for &v in &references {
+            let y = to_y(v).round();
+            self.draw_list.add_line(float2(inner.min.0, y), float2(inner.max.0, y), reference);
+        }
+
+        let text = self.style.plot.text;
+        self.draw_list.add_text_wrapped(
+            &mut *self.renderer,
+            &mut self.default_font,
+            label,
+            inner.min,
+            text.align,
+            inner.width(),
+            text.color
+        );
+
+        self.draw_list.pop_clip_rect();
+
+        if let Some(idx) = hovered {
+            self.plot_tooltip(&format!("{}: {:.3}", idx, values[idx]));
+        }
+    }
+
+    fn plot_tooltip(&mut self, text: &str) {
+        let style = &self.style.plot;
+        let (text_style, background) = (style.tooltip_text, style.tooltip_background);
+        let padding = float2(4f32, 2f32);
+
+        let width = self.default_font.text_width(&mut *self.renderer, text);
+        let min = self.io.mouse + float2(12f32, 12f32);
+        let max = min + float2(width, self.default_font.advance_y()) + padding * 2f32;
+
+        self.draw_list.push_full_clip_rect();
+        self.draw_list.add_rect_filled(min, max, 2f32, background);
+        self.draw_list.add_text_wrapped(
+            &mut *self.renderer,
+            &mut self.default_font,
+            text,
+            min + padding,
+            TextAlignment::Left,
+            width + 1f32,
+            text_style.color
+        );
+        self.draw_list.pop_clip_rect();
+    }
+}
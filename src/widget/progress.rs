@@ -0,0 +1,105 @@
+use crate::{
+    Context,
+    Background,
+    WidgetLayoutState,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+const INDETERMINATE_WIDTH: f32 = 0.3f32;
+const INDETERMINATE_SPEED: f32 = 0.8f32;
+const SPINNER_SPEED: f32 = 1.5f32;
+const SPINNER_SEGMENTS: u32 = 24;
+
+impl Context {
+    /// Bar filled to `fraction` (0..1), or an animated bar moving back and
+    /// forth when `fraction` is `None`. Without `overlay` determinate bars
+    /// show their percentage.
+    pub fn progress_bar(&mut self, fraction: Option<f32>, overlay: Option<&str>) {
+        let padding = self.style.progress.padding;
+        let h = self.default_font.height() + padding.1 * 2f32;
+        let (bounds, state) = self.widget(Some(h));
+        if state == WidgetLayoutState::Hidden {
+            return;
+        }
+
+        let style = &self.style.progress;
+        let (border, background, bar) = (style.border, style.background, style.bar);
+        self.draw_button(bounds, border, background);
+
+        let inner = bounds.pad_sides(padding.0, padding.1, padding.0, padding.1);
+        let filled = match fraction {
+            Some(fraction) => {
+                let fraction = fraction.max(0f32).min(1f32);
+                Rect::new(inner.min, float2(inner.min.0 + inner.width() * fraction, inner.max.1))
+            }
+            None => {
+                // slides from just outside the left edge to just outside the right one
+                let t = (self.io.time * INDETERMINATE_SPEED).fract();
+                let width = inner.width() * INDETERMINATE_WIDTH;
+                let x = inner.min.0 - width + t * (inner.width() + width);
+
+                Rect::new(float2(x, inner.min.1), float2(x + width, inner.max.1))
+            }
+        };
+
+        self.draw_list.push_clip_rect(inner.to_float4());
+        if filled.width() > 0f32 {
+            match bar {
+                Background::Color(col) => {
+                    self.draw_list.add_rect_filled(filled.min, filled.max, 0f32, col);
+                },
+                Background::NineSlice(image) => {
+                    self.draw_list.add_nine_slice(&image, filled, 0xffffffff);
+                },
+                _ => {}
+            }
+        }
+
+        let percentage;
+        let text = match (overlay, fraction) {
+            (Some(text), _) => Some(text),
+            (None, Some(fraction)) => {
+                percentage = format!("{:.0}%", fraction.max(0f32).min(1f32) * 100f32);
+                Some(percentage.as_str())
+            }
+            (None, None) => None,
+        };
+
+        if let Some(text) = text {
+            let style = self.style.progress.text;
+            let y = inner.min.1 + ((inner.height() - self.default_font.advance_y()) * 0.5f32).round();
+            self.draw_list.add_text_wrapped(
+                &mut *self.renderer,
+                &mut self.default_font,
+                text,
+                float2(inner.min.0, y),
+                style.align,
+                inner.width(),
+                style.color
+            );
+        }
+        self.draw_list.pop_clip_rect();
+    }
+
+    /// Rotating arc of `radius`, placed at the start of the next widget slot.
+    pub fn spinner(&mut self, radius: f32) {
+        let thickness = self.style.progress.spinner_thickness;
+        let color = self.style.progress.spinner;
+        let (bounds, state) = self.widget(Some(radius * 2f32 + thickness));
+        if state == WidgetLayoutState::Hidden {
+            return;
+        }
+
+        let center = bounds.min + float2(radius, radius) + float2(thickness, thickness) * 0.5f32;
+        let tau = ::std::f32::consts::PI * 2f32;
+        let start = (self.io.time * SPINNER_SPEED).fract() * tau;
+
+        self.draw_list.path()
+            .arc(center, radius, start, start + tau * 0.75f32, SPINNER_SEGMENTS)
+            .stroke(thickness, false, color);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{
+    Context, MouseButton,
+    Background,
+    WindowFlags,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+use super::{
+    WidgetState,
+};
+
+#[derive(Copy, Clone)]
+pub struct ScrollState {
+    pub offset: float2,
+    pub content_size: float2,
+}
+
+impl Default for ScrollState {
+    fn default() -> Self {
+        ScrollState::new()
+    }
+}
+
+impl ScrollState {
+    pub fn new() -> Self {
+        ScrollState {
+            offset: float2(0f32, 0f32),
+            content_size: float2(0f32, 0f32),
+        }
+    }
+}
+
+/// Scroll offset that puts the row starting at `row_y` in content space at
+/// `ratio` of a view `view` tall.
+fn scroll_here_offset(row_y: f32, row_height: f32, view: f32, ratio: f32) -> f32 {
+    let ratio = ratio.max(0f32).min(1f32);
+
+    row_y + row_height * ratio - view * ratio
+}
+
+impl Context {
+    /// Size of the scrollbars to reserve for a panel with the given view
+    /// and the content size measured during the previous frame.
+    pub(crate) fn panel_scrollbar_size(&self, view: Rect, content: float2, flags: WindowFlags) -> float2 {
+        if flags.contains(WindowFlags::NoScrollbar) || flags.contains(WindowFlags::ScrollAutoHide) {
+            return float2(0f32, 0f32);
+        }
+
+        let size = self.style.window.scrollbar_size;
+
+        let mut show_y = content.1 > view.height();
+        let show_x = content.0 > view.width() - if show_y { size.0 } else { 0f32 };
+        if show_x && !show_y {
+            show_y = content.1 > view.height() - size.1;
+        }
+
+        float2(
+            if show_y { size.0 } else { 0f32 },
+            if show_x { size.1 } else { 0f32 },
+        )
+    }
+
+    pub(crate) fn panel_scrollbars(&mut self) {
+        let idx = self.current_panel;
+        let panel = &self.panel_stack[idx];
+
+        let id = panel.id;
+        let flags = panel.flags;
+        let view = panel.bounds;
+        let clip = panel.clip;
+        let reserved = panel.scrollbar;
+        let padding = panel.get_padding(&self.style.window);
+        let origin = panel.bounds.min - panel.offset;
+        // trailing padding on both axes, so the last widget doesn't touch
+        // the edge when scrolled to the end
+        let content = float2(
+            (panel.max_x + padding.0 - origin.0).max(0f32),
+            (panel.max_y + padding.1 - origin.1).max(0f32)
+        );
+
+        let state_idx = self.scroll_states.get(id);
+        let mut state = self.scroll_states[state_idx];
+        state.content_size = content;
+
+        let max_scroll = float2(
+            (content.0 - view.width()).max(0f32),
+            (content.1 - view.height()).max(0f32)
+        );
+
+        if !flags.contains(WindowFlags::NoScrollbar) {
+            let io = &self.io;
+            if !self.scroll_consumed && io.has_mouse_in_rect(clip) {
+                let step = self.default_font.height() * 3f32;
+                let wheel = io.mouse_scroll;
+
+                if wheel.1 != 0f32 && max_scroll.1 > 0f32 {
+                    state.offset.1 -= wheel.1 * step;
+                    self.scroll_consumed = true;
+                } else if wheel.0 != 0f32 && max_scroll.0 > 0f32 {
+                    state.offset.0 -= wheel.0 * step;
+                    self.scroll_consumed = true;
+                }
+            }
+
+            let size = self.style.window.scrollbar_size;
+            let (show_x, show_y) = if flags.contains(WindowFlags::ScrollAutoHide) {
+                let visible = io.has_mouse_in_rect(clip)
+                    || (io.is_mouse_down(MouseButton::Left) && io.has_mouse_click_in_rect(MouseButton::Left, clip));
+
+                (visible && max_scroll.0 > 0f32, visible && max_scroll.1 > 0f32)
+            } else {
+                (reserved.1 > 0f32, reserved.0 > 0f32)
+            };
+            let corner = float2(
+                if show_y { size.0 } else { 0f32 },
+                if show_x { size.1 } else { 0f32 },
+            );
+
+            if show_y {
+                let track = Rect::new(
+                    float2(clip.max.0 - size.0, clip.min.1),
+                    float2(clip.max.0, clip.max.1 - corner.1)
+                );
+                state.offset.1 = self.scrollbar(track, state.offset.1, content.1, view.height(), true);
+            }
+
+            if show_x {
+                let track = Rect::new(
+                    float2(clip.min.0, clip.max.1 - size.1),
+                    float2(clip.max.0 - corner.0, clip.max.1)
+                );
+                state.offset.0 = self.scrollbar(track, state.offset.0, content.0, view.width(), false);
+            }
+        }
+
+        state.offset.0 = state.offset.0.max(0f32).min(max_scroll.0).round();
+        state.offset.1 = state.offset.1.max(0f32).min(max_scroll.1).round();
+
+        self.scroll_states[state_idx] = state;
+    }
+
+    fn scrollbar(&mut self, track: Rect, offset: f32, content: f32, view: f32, vertical: bool) -> f32 {
+        let axis = |v: float2| if vertical { v.1 } else { v.0 };
+
+        let style = &self.style.scrollbar;
+        let inner = track.pad(style.padding.0.min(style.padding.1));
+
+        let track_len = axis(inner.max - inner.min);
+        let max_scroll = (content - view).max(0f32);
+        if track_len <= 0f32 {
+            return offset;
+        }
+
+        let thumb_len = if content > 0f32 {
+            (track_len * (view / content)).max(16f32).min(track_len)
+        } else {
+            track_len
+        };
+        let travel = track_len - thumb_len;
+
+        let thumb_bounds = |offset: f32| {
+            let t = if max_scroll > 0f32 { offset / max_scroll } else { 0f32 };
+            let start = axis(inner.min) + (t * travel).round();
+
+            if vertical {
+                Rect::new(float2(inner.min.0, start), float2(inner.max.0, start + thumb_len))
+            } else {
+                Rect::new(float2(start, inner.min.1), float2(start + thumb_len, inner.max.1))
+            }
+        };
+
+        let mut offset = offset;
+        let mut thumb = thumb_bounds(offset);
+        let mut state = WidgetState::None;
+
+        let io = &mut self.io;
+        if io.has_mouse_in_rect(thumb) {
+            state = WidgetState::Hovering;
+        }
+
+        if io.is_mouse_down(MouseButton::Left) && io.has_mouse_click_in_rect(MouseButton::Left, thumb) {
+            state = WidgetState::Active;
+
+            let delta = axis(io.mouse_delta);
+            if travel > 0f32 && delta != 0f32 {
+                offset += delta * (max_scroll / travel);
+            }
+
+            if vertical {
+                io.mouse_clicked_pos[MouseButton::Left as usize].1 += delta;
+            } else {
+                io.mouse_clicked_pos[MouseButton::Left as usize].0 += delta;
+            }
+        } else if io.is_mouse_pressed(MouseButton::Left) && io.has_mouse_in_rect(track) {
+            // page towards the click
+            if axis(io.mouse) < axis(thumb.min) {
+                offset -= view;
+            } else if axis(io.mouse) > axis(thumb.max) {
+                offset += view;
+            }
+        }
+
+        let offset = offset.max(0f32).min(max_scroll);
+        thumb = thumb_bounds(offset);
+
+        let style = &self.style.scrollbar;
+        let (border, background) = if state.contains(WidgetState::Active) {
+            (style.active_border, style.active)
+        } else if state.contains(WidgetState::Hovering) {
+            (style.hover_border, style.hover)
+        } else {
+            (style.normal_border, style.normal)
+        };
+
+        match style.background {
+            Background::Color(col) => {
+                self.draw_list.add_rect_filled(track.min, track.max, 0f32, col);
+            },
+            _ => {}
+        }
+        self.draw_button(thumb, border, background);
+
+        offset
+    }
+
+    /// Scrolls the current panel vertically to `y` pixels from the top of
+    /// its content. The new offset is applied from the next frame.
+    pub fn set_scroll_y(&mut self, y: f32) {
+        let id = self.panel_stack[self.current_panel].id;
+        let idx = self.scroll_states.get(id);
+
+        self.scroll_states[idx].offset.1 = y.max(0f32);
+    }
+
+    pub fn scroll_y(&mut self) -> f32 {
+        let id = self.panel_stack[self.current_panel].id;
+        let idx = self.scroll_states.get(id);
+
+        self.scroll_states[idx].offset.1
+    }
+
+    /// Scrolls the current panel so the current row ends up at
+    /// `center_y_ratio` of the view (0.0 top, 0.5 center, 1.0 bottom).
+    pub fn scroll_here(&mut self, center_y_ratio: f32) {
+        let panel = &self.panel_stack[self.current_panel];
+
+        let origin_y = panel.bounds.min.1 - panel.offset.1;
+        let target = scroll_here_offset(
+            panel.cursor.1 - origin_y,
+            panel.row.height,
+            panel.bounds.height(),
+            center_y_ratio
+        );
+
+        self.set_scroll_y(target);
+    }
+}
+
+#[test]
+fn scroll_here_offsets() {
+    // row from 200 to 220 in a 100 tall view
+    assert_eq!(scroll_here_offset(200f32, 20f32, 100f32, 0f32), 200f32);
+    assert_eq!(scroll_here_offset(200f32, 20f32, 100f32, 0.5f32), 160f32);
+    assert_eq!(scroll_here_offset(200f32, 20f32, 100f32, 1f32), 120f32);
+}
@@ -0,0 +1,110 @@
+use crate::{
+    Context, MouseButton,
+    Background,
+    CursorType,
+
+    math::{
+        float2,
+        Rect,
+    }
+};
+
+const SPLITTER_GRAB_SIZE: f32 = 6f32;
+const SPLITTER_MIN_SIZE: f32 = 32f32;
+
+impl Context {
+    /// Draggable bar between the current and the next column of a dynamic
+    /// row. Call it after the left pane, `ratio` is the width of the left
+    /// column relative to the row. The ratio is kept in the context's
+    /// storage under `id`, `ratio` only provides its initial value.
+    pub fn splitter_horizontal(&mut self, id: &str, ratio: &mut f32) -> bool {
+        let panel = &self.panel_stack[self.current_panel];
+        let padding = panel.get_padding(&self.style.window);
+        let spacing = self.style.window.spacing;
+        let total = panel.calculate_usable_space(&self.style.window);
+
+        let x = panel.cursor.0 + padding.0 + panel.row.item_offset - spacing.0 * 0.5f32;
+        let bar = Rect::new(
+            float2(x, panel.cursor.1),
+            float2(x, panel.cursor.1 + panel.row.height - spacing.1)
+        );
+
+        self.splitter(id, bar, total, ratio, false)
+    }
+
+    /// Draggable bar below the current row. Call it after the top pane,
+    /// `ratio` is the height of that row relative to the height that was
+    /// available when it was started.
+    pub fn splitter_vertical(&mut self, id: &str, ratio: &mut f32) -> bool {
+        let panel = &self.panel_stack[self.current_panel];
+        let padding = panel.get_padding(&self.style.window);
+        let spacing = self.style.window.spacing;
+        let total = panel.bounds.max.1 - panel.cursor.1;
+
+        let y = panel.cursor.1 + panel.row.height - spacing.1 * 0.5f32;
+        let bar = Rect::new(
+            float2(panel.bounds.min.0 + padding.0, y),
+            float2(panel.bounds.max.0 - padding.0, y)
+        );
+
+        self.splitter(id, bar, total, ratio, true)
+    }
+
+    fn splitter(&mut self, id: &str, bar: Rect, total: f32, ratio: &mut f32, vertical: bool) -> bool {
+        let id = self.id(id);
+        let stored = self.storage.get_float_ref(id, *ratio);
+        *ratio = unsafe { *stored };
+
+        let half = -SPLITTER_GRAB_SIZE * 0.5f32;
+        let grab = if vertical {
+            bar.grow(float2(0f32, half), float2(0f32, half))
+        } else {
+            bar.grow(float2(half, 0f32), float2(half, 0f32))
+        };
+
+        let io = &mut self.io;
+        let hovered = io.has_mouse_in_rect(grab);
+        let dragging = io.is_mouse_down(MouseButton::Left) && io.has_mouse_click_in_rect(MouseButton::Left, grab);
+
+        let mut changed = false;
+        if dragging && total > 0f32 {
+            let delta = if vertical { io.mouse_delta.1 } else { io.mouse_delta.0 };
+            let min = (SPLITTER_MIN_SIZE / total).min(0.5f32);
+            let new_ratio = ((*ratio * total + delta) / total).max(min).min(1f32 - min);
+            let applied = (new_ratio - *ratio) * total;
+
+            // keep the click anchored to the bar so it follows the mouse
+            if vertical {
+                io.mouse_clicked_pos[MouseButton::Left as usize].1 += applied;
+            } else {
+                io.mouse_clicked_pos[MouseButton::Left as usize].0 += applied;
+            }
+
+            changed = new_ratio != *ratio;
+            *ratio = new_ratio;
+            unsafe { *stored = new_ratio; }
+        }
+
+        if hovered || dragging {
+            self.cursor = if vertical {
+                CursorType::ResizeVertical
+            } else {
+                CursorType::ResizeHorizontal
+            };
+        }
+
+        let style = &self.style.button;
+        let background = if dragging {
+            style.active
+        } else if hovered {
+            style.hover
+        } else {
+            style.normal
+        };
+        if let Background::Color(col) = background {
+            self.draw_list.add_rect_filled(grab.min, grab.max, 0f32, col);
+        }
+
+        changed
+    }
+}
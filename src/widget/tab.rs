@@ -10,7 +10,9 @@ use crate::{
     Id,
     PanelFlags,
     PoolIndex,
+    SettingsSection,
     hash_id,
+    parse_id,
     
     math::{
         float2,
@@ -20,14 +22,38 @@ use crate::{
 
 use super::{
     WidgetState,
+    window::WindowHeaderButton,
 };
 
+bitflags! {
+    pub struct TabBarFlags: u32 {
+        const None = 0;
+        /// Tabs can be dragged to change their order.
+        const Reorderable = 1 << 0;
+        /// Newly submitted tabs become the selected tab.
+        const AutoSelectNewTabs = 1 << 1;
+        /// Shrink tabs to fit when they overflow the bar.
+        const FittingPolicyResize = 1 << 2;
+        /// Keep tab widths and show scroll arrows when they overflow.
+        const FittingPolicyScroll = 1 << 3;
+    }
+}
+
+bitflags! {
+    pub struct TabItemFlags: u32 {
+        const None = 0;
+        /// Make this tab the selected one, takes effect on the next frame.
+        const SetSelected = 1 << 0;
+    }
+}
+
 #[derive(Clone)]
 pub struct TabBar {
     id: Id,
     selected_id: Id,
     bounds: Rect,
     tabs: Vec<TabItem>,
+    flags: TabBarFlags,
 
     want_layout: bool,
     prev_frame_visible: u32,
@@ -38,6 +64,13 @@ pub struct TabBar {
     next_selected_tab: Id,
     offset_next_tab: f32,
     offset_max: f32,
+
+    scroll_offset: f32,
+    visible_width: f32,
+    reorder_id: Id,
+    reorder_request_id: Id,
+    reorder_request_dir: isize,
+    selection_changed: bool,
 }
 
 impl Default for TabBar {
@@ -53,6 +86,7 @@ impl TabBar {
             selected_id: 0,
             bounds: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
             tabs: Vec::new(),
+            flags: TabBarFlags::None,
 
             want_layout: false,
             prev_frame_visible: 0,
@@ -63,8 +97,32 @@ impl TabBar {
             next_selected_tab: 0,
             offset_next_tab: 0f32,
             offset_max: 0f32,
+
+            scroll_offset: 0f32,
+            visible_width: 0f32,
+            reorder_id: 0,
+            reorder_request_id: 0,
+            reorder_request_dir: 0,
+            selection_changed: false,
         }
     }
+
+    fn max_scroll(&self) -> f32 {
+        (self.offset_max - self.visible_width).max(0f32)
+    }
+
+    /// Scrolls just enough to make the tab at `idx` fully visible.
+    fn scroll_to_tab(&mut self, idx: usize) {
+        let tab = &self.tabs[idx];
+
+        if tab.offset < self.scroll_offset {
+            self.scroll_offset = tab.offset;
+        } else if tab.offset + tab.width > self.scroll_offset + self.visible_width {
+            self.scroll_offset = tab.offset + tab.width - self.visible_width;
+        }
+
+        self.scroll_offset = self.scroll_offset.max(0f32).min(self.max_scroll());
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -80,6 +138,7 @@ pub struct TabItem {
     width: f32,
     offset: f32,
     name: String,
+    closable: bool,
 
     last_frame_visible: u32,
 }
@@ -92,6 +151,7 @@ impl TabItem {
             width: 0f32,
             offset: 0f32,
             name: String::new(),
+            closable: false,
 
             last_frame_visible: 0,
         }
@@ -102,11 +162,14 @@ impl TabItem {
 
 impl Context {
     pub fn begin_tab_bar(&mut self, title: &str) -> bool {
+        self.begin_tab_bar_ex(title, TabBarFlags::FittingPolicyResize)
+    }
+
+    pub fn begin_tab_bar_ex(&mut self, title: &str, flags: TabBarFlags) -> bool {
         let hash = hash_id(title);
         let tab_idx = self.tab_bars.get(hash);
         
-        let padding = self.style.tab.padding;
-        let tab_height = 20f32 + padding.1 * 2f32;
+        let tab_height = self.style.tab.tab_height();
 
         self.row(RowType::dynamic(1));
         self.column(Some(1f32));
@@ -117,6 +180,7 @@ impl Context {
 
         let tab = &mut self.tab_bars[tab_idx];
         tab.id = hash;
+        tab.flags = flags;
         self.current_tab_bar.push(tab_idx);
         tab.bounds = bounds;
         tab.want_layout = true;
@@ -131,18 +195,35 @@ impl Context {
     }
 
     pub fn end_tab_bar(&mut self) {
-        if let Some(idx) = self.current_tab_bar.last() {
-            let tab = &mut self.tab_bars[*idx];
+        if let Some(&idx) = self.current_tab_bar.last() {
+            let tab = &mut self.tab_bars[idx];
             if tab.want_layout {
-                self.tab_bar_layout(*idx);
+                self.tab_bar_layout(idx);
             }
+
+            self.tab_bar_scroll_arrows(idx);
         }
         
         self.current_tab_bar.pop();
     }
 
     pub fn begin_tab_item(&mut self, title: &str) -> bool {
+        self.begin_tab_item_ex(title, None, TabItemFlags::None)
+    }
+
+    /// Tab item with a close button, `open` is set to false when it gets
+    /// clicked. Closed tabs are not shown and `false` is returned.
+    pub fn begin_tab_item_open(&mut self, title: &str, open: &mut bool) -> bool {
+        self.begin_tab_item_ex(title, Some(open), TabItemFlags::None)
+    }
+
+    pub fn begin_tab_item_ex(&mut self, title: &str, open: Option<&mut bool>, flags: TabItemFlags) -> bool {
+        if let Some(false) = open.as_ref().map(|open| **open) {
+            return false;
+        }
+
         let id = self.id(title);
+        let closable = open.is_some();
         let width = self.default_font.text_width(&mut *self.renderer, title);
         let padding = self.style.tab.padding;
         self.last_widget_state = WidgetState::None;
@@ -161,23 +242,39 @@ impl Context {
         let tab_idx = self.find_tab(tab_bar_idx, id);
         let tab_bar = &mut self.tab_bars[tab_bar_idx];
 
+        let tab_height = self.style.tab.tab_height();
+        let close_size = (tab_height * 0.5f32).floor();
+        let close_spacing = padding.0.max(4f32);
+
         let (is_new, tab_idx) = if let Some(idx) = tab_idx {
             (false, idx)
         } else {
             let mut item = TabItem::new();
             item.id = id;
-            item.width = width + padding.0 * 2f32;;
+            item.width = width + padding.0 * 2f32;
+            if closable {
+                item.width += close_size + close_spacing;
+            }
             item.name += title;
             let idx = tab_bar.tabs.len();
             
+            // the first tab is selected anyway
+            if tab_bar.flags.contains(TabBarFlags::AutoSelectNewTabs) && idx > 0 {
+                tab_bar.next_selected_tab = id;
+            }
+
             tab_bar.tabs.push(item);
             
             (true, idx)
         };
 
-        let padding = self.style.tab.padding;
+        if flags.contains(TabItemFlags::SetSelected) {
+            tab_bar.next_selected_tab = id;
+        }
+
         let tab = &mut tab_bar.tabs[tab_idx];
         tab.last_frame_visible = self.frame;
+        tab.closable = closable;
 
         /*tab.offset = tab_bar.offset_next_tab;
         tab_bar.offset_next_tab = tab.width + padding.0 * 2f32;*/
@@ -185,22 +282,53 @@ impl Context {
         let width = tab.width;
         let offset = tab.offset;
 
-        let tab_height = 20f32 + padding.1 * 2f32;
         //self.column_fixed(width);
         //let (bounds, state) = self.widget(Some(tab_height));
-        let origin = tab_bar.bounds.min + float2(offset, 0f32);
+        let origin = tab_bar.bounds.min + float2(offset - tab_bar.scroll_offset, 0f32);
         let bounds = Rect::new(origin, origin + float2(width, tab_height));
+        let tabs_area = Rect::new(
+            tab_bar.bounds.min,
+            float2(tab_bar.bounds.min.0 + tab_bar.visible_width, tab_bar.bounds.min.1 + tab_height)
+        );
+        let reorderable = tab_bar.flags.contains(TabBarFlags::Reorderable);
  
         drop(tab_bar); // borrowck
 
-        let pressed = self.button_behaviour(bounds, ButtonFlags::PressOnClick);
+        let close_min = float2(
+            bounds.max.0 - close_spacing - close_size,
+            bounds.min.1 + ((tab_height - close_size) * 0.5f32).floor()
+        );
+        let close_bounds = Rect::new(close_min, close_min + float2(close_size, close_size));
+        let on_close = closable && self.io.has_mouse_in_rect(close_bounds);
+
+        let pressed = match bounds.clip(tabs_area) {
+            Some(visible) => self.button_behaviour(visible, ButtonFlags::PressOnClick) && !on_close,
+            None => false,
+        };
         
         let index = self.current_index();
         let wnd = unsafe { self.windows.get_unchecked(index) };
+        let read_only = wnd.flags.contains(WindowFlags::ReadOnly);
         let tab_bar = &mut self.tab_bars[tab_bar_idx];
 
-        if pressed && !wnd.flags.contains(WindowFlags::ReadOnly) {
+        if pressed && !read_only {
             tab_bar.next_selected_tab = id;
+
+            if reorderable {
+                tab_bar.reorder_id = id;
+            }
+        }
+
+        // swap with a neighbour once the mouse leaves the dragged tab
+        if tab_bar.reorder_id == id && self.io.is_mouse_down(MouseButton::Left) {
+            let io = &self.io;
+            if io.mouse.0 < bounds.min.0 && io.mouse_delta.0 < 0f32 && tab_idx > 0 {
+                tab_bar.reorder_request_id = id;
+                tab_bar.reorder_request_dir = -1;
+            } else if io.mouse.0 > bounds.max.0 && io.mouse_delta.0 > 0f32 && tab_idx + 1 < tab_bar.tabs.len() {
+                tab_bar.reorder_request_id = id;
+                tab_bar.reorder_request_dir = 1;
+            }
         }
         
         let style = &self.style.tab;
@@ -213,8 +341,24 @@ impl Context {
         } else {
             (style.normal_text, style.normal_border, style.normal)
         };
+
+        let mut text_bounds = bounds;
+        if closable {
+            text_bounds.max.0 = close_bounds.min.0;
+        }
+
+        self.draw_list.push_clip_rect(tabs_area.to_float4());
         self.tab_item_bg(bounds, background, border);
-        self.tab_item_text(title, bounds, text);
+        self.tab_item_text(title, text_bounds, text);
+
+        if closable && (is_current || state.contains(WidgetState::Hovering) || on_close) && !read_only {
+            if self.window_header_button(close_bounds, WindowHeaderButton::Close, text.color) {
+                if let Some(open) = open {
+                    *open = false;
+                }
+            }
+        }
+        self.draw_list.pop_clip_rect();
         
         if self.io.is_mouse_down(MouseButton::Right) {
             self.draw_list.add_rect(bounds.min, bounds.max, 0f32, 1f32, 0x8800ffff);
@@ -239,35 +383,45 @@ impl Context {
         let tab_bar = &mut self.tab_bars[tab_bar_idx];
         tab_bar.want_layout = false;
         
-        // garbage collect
-        let mut tab_dst_n = 0;
-        let mut i = 0;
-        let len = tab_bar.tabs.len();
-        while i < len {
-            let tab = &tab_bar.tabs[i];
-            
-            if tab.last_frame_visible < tab_bar.prev_frame_visible {
-                if tab.id == tab_bar.selected_id {
-                    tab_bar.selected_id = 0;
-                    continue;
-                }
+        // garbage collect tabs that were not submitted last frame
+        let prev_frame_visible = tab_bar.prev_frame_visible;
+        let selected_tab_id = tab_bar.selected_tab_id;
+        let mut selected_removed = false;
+        tab_bar.tabs.retain(|tab| {
+            let keep = tab.last_frame_visible >= prev_frame_visible;
+            if !keep && tab.id == selected_tab_id {
+                selected_removed = true;
             }
 
-            if tab_dst_n != i {
-                tab_bar.tabs.swap(tab_dst_n, i);
-                // tab_bar.tabs[tab_dst_n] = *tab;
-            }
-            
-            tab_dst_n += 1;
-            i += 1;
+            keep
+        });
+
+        if selected_removed {
+            tab_bar.selected_tab_id = 0;
         }
 
-        if tab_bar.tabs.len() != tab_dst_n {
-            tab_bar.tabs.truncate(tab_dst_n);
+        // commit reordering
+        if !self.io.is_mouse_down(MouseButton::Left) {
+            tab_bar.reorder_id = 0;
+        }
+
+        if tab_bar.reorder_request_id != 0 {
+            let id = tab_bar.reorder_request_id;
+            let dir = tab_bar.reorder_request_dir;
+            tab_bar.reorder_request_id = 0;
+
+            if let Some(idx) = tab_bar.tabs.iter().position(|tab| tab.id == id) {
+                let other = idx as isize + dir;
+                if other >= 0 && (other as usize) < tab_bar.tabs.len() {
+                    tab_bar.tabs.swap(idx, other as usize);
+                }
+            }
         }
 
         // commit tab selection
+        let mut selection_changed = false;
         if tab_bar.next_selected_tab != 0 {
+            selection_changed = tab_bar.selected_tab_id != tab_bar.next_selected_tab;
             tab_bar.selected_tab_id = tab_bar.next_selected_tab;
             tab_bar.next_selected_tab = 0;
         }
@@ -284,6 +438,10 @@ impl Context {
             width: 0f32
         });
         
+        let padding = self.style.tab.padding;
+        let tab_height = self.style.tab.tab_height();
+        let close_width = (tab_height * 0.5f32).floor() + padding.0.max(4f32);
+
         while i < len {
             let tab = &mut tab_bar.tabs[i];
 
@@ -293,8 +451,10 @@ impl Context {
 
             let name = &tab.name;
 
-            let padding = self.style.tab.padding;
             tab.content_width = self.default_font.text_width(&mut *self.renderer, name) + padding.0 * 2f32;
+            if tab.closable {
+                tab.content_width += close_width;
+            }
             total_width += tab.content_width + 2f32;
 
             self.tab_sorter[i] = TabItemSort {
@@ -305,6 +465,7 @@ impl Context {
             i += 1;
         }
 
+        let scrolling = tab_bar.flags.contains(TabBarFlags::FittingPolicyScroll);
         let width_available = tab_bar.bounds.width();
         let mut excess = if width_available < total_width {
             total_width - width_available
@@ -314,7 +475,7 @@ impl Context {
         let tabs_len = tab_bar.tabs.len();
 
         // if we overflow, shrink as much as possible
-        if excess > 0f32 {
+        if excess > 0f32 && !scrolling {
             if tab_bar.tabs.len() > 1 {
                 self.tab_sorter.sort_by(
                     |a, b| {
@@ -380,9 +541,157 @@ impl Context {
         tab_bar.offset_max = (offset_x - spacing_x).max(0f32);
         tab_bar.offset_next_tab = 0f32;
 
+        // leave room for the scroll arrows
+        tab_bar.visible_width = if scrolling && tab_bar.offset_max > width_available {
+            (width_available - tab_height * 2f32).max(0f32)
+        } else {
+            width_available
+        };
+        tab_bar.scroll_offset = tab_bar.scroll_offset.max(0f32).min(tab_bar.max_scroll());
+
+        if selection_changed {
+            let selected = tab_bar.selected_tab_id;
+            if let Some(idx) = tab_bar.tabs.iter().position(|tab| tab.id == selected) {
+                tab_bar.scroll_to_tab(idx);
+            }
+        }
+
         if !found {
             tab_bar.selected_id = 0;
         }
+
+        tab_bar.selection_changed = selection_changed;
+        if selection_changed {
+            self.mark_settings_dirty();
+        }
+    }
+
+    /// Selects the tab titled `title` of the tab bar `bar` on the next
+    /// frame. The tab bar and tab don't need to exist yet.
+    pub fn set_selected_tab(&mut self, bar: &str, title: &str) {
+        let idx = self.tab_bars.get(hash_id(bar));
+        let id = self.id(title);
+
+        let tab_bar = &mut self.tab_bars[idx];
+        tab_bar.id = hash_id(bar);
+        tab_bar.next_selected_tab = id;
+    }
+
+    /// Title of the selected tab of `bar`, if it has been submitted.
+    pub fn selected_tab(&self, bar: &str) -> Option<&str> {
+        let tab_bar = &self.tab_bars[self.tab_bars.find(hash_id(bar))?];
+
+        tab_bar.tabs.iter()
+            .find(|tab| tab.id == tab_bar.selected_tab_id)
+            .map(|tab| tab.name.as_str())
+    }
+
+    /// True during the frame in which the selected tab of `bar` changed,
+    /// once its first tab item has been submitted.
+    pub fn tab_changed(&self, bar: &str) -> bool {
+        match self.tab_bars.find(hash_id(bar)) {
+            Some(idx) => {
+                let tab_bar = &self.tab_bars[idx];
+                tab_bar.selection_changed && tab_bar.curr_frame_visible == self.frame
+            },
+            None => false,
+        }
+    }
+
+    fn tab_bar_scroll_arrows(&mut self, tab_bar_idx: PoolIndex) {
+        let tab_bar = &self.tab_bars[tab_bar_idx];
+        let bounds = tab_bar.bounds;
+        let visible_width = tab_bar.visible_width;
+        if visible_width >= bounds.width() {
+            return;
+        }
+
+        let size = (bounds.width() - visible_width) * 0.5f32;
+        let left_min = float2(bounds.min.0 + visible_width, bounds.min.1);
+        let left = Rect::new(left_min, left_min + float2(size, size));
+        let right = Rect::new(left_min + float2(size, 0f32), left_min + float2(size * 2f32, size));
+        let color = self.style.tab.normal_text.color;
+
+        let mut dir = 0;
+        for &(arrow, step) in &[(left, -1), (right, 1)] {
+            self.last_widget_state = WidgetState::None;
+            if self.button_behaviour(arrow, ButtonFlags::None) {
+                dir = step;
+            }
+
+            let style = &self.style.button;
+            let state = self.last_widget_state;
+            let (border, background) = if state.contains(WidgetState::Active) {
+                (style.active_border, style.active)
+            } else if state.contains(WidgetState::Hovering) {
+                (style.hover_border, style.hover)
+            } else {
+                (style.normal_border, style.normal)
+            };
+            self.draw_button(arrow, border, background);
+
+            let inner = arrow.pad((size * 0.3f32).floor());
+            let (tip, back) = if step < 0 {
+                (inner.min.0, inner.max.0)
+            } else {
+                (inner.max.0, inner.min.0)
+            };
+            self.draw_list.path()
+                .line(float2(back, inner.min.1))
+                .line(float2(tip, inner.center().1))
+                .line(float2(back, inner.max.1))
+                .fill(color);
+        }
+
+        if dir == 0 {
+            return;
+        }
+
+        // step to the start of the next partially hidden tab
+        let tab_bar = &mut self.tab_bars[tab_bar_idx];
+        let scroll = tab_bar.scroll_offset;
+        let target = if dir < 0 {
+            tab_bar.tabs.iter().rposition(|tab| tab.offset < scroll)
+        } else {
+            tab_bar.tabs.iter().position(|tab| tab.offset + tab.width > scroll + visible_width)
+        };
+
+        if let Some(idx) = target {
+            tab_bar.scroll_to_tab(idx);
+        }
+    }
+
+    pub(crate) fn save_tab_bar_settings(&self, out: &mut String) {
+        use std::fmt::Write;
+
+        for tab_bar in self.tab_bars.iter() {
+            if tab_bar.id == 0 || tab_bar.selected_tab_id == 0 {
+                continue;
+            }
+
+            let _ = writeln!(out, "[TabBar][0x{:08x}]", tab_bar.id);
+            let _ = writeln!(out, "Selected=0x{:08x}", tab_bar.selected_tab_id);
+            out.push('\n');
+        }
+    }
+
+    pub(crate) fn load_tab_bar_settings(&mut self, section: &SettingsSection) {
+        let id = match parse_id(section.name) {
+            Some(id) => id,
+            None => return,
+        };
+
+        let idx = self.tab_bars.get(id);
+        let tab_bar = &mut self.tab_bars[idx];
+        tab_bar.id = id;
+
+        for &(key, val) in &section.entries {
+            if key == "Selected" {
+                if let Some(selected) = parse_id(val) {
+                    tab_bar.selected_tab_id = selected;
+                }
+            }
+        }
     }
     
     fn find_tab(&self, tab_bar_idx: PoolIndex, id: Id) -> Option<usize> {
@@ -403,7 +712,7 @@ impl Context {
         None
     }
 
-    fn tab_item_bg(&mut self, bounds: Rect, bg: Background, border: Border) {
+    pub(crate) fn tab_item_bg(&mut self, bounds: Rect, bg: Background, border: Border) {
         let rounding = border.rounding;
 
         let bmin = bounds.min.round();
@@ -421,6 +730,10 @@ impl Context {
                     .line(float2(bmax.0, y2))
                     .fill(c);
             }
+            Background::NineSlice(image) => {
+                let rect = Rect::new(float2(bmin.0, y1), float2(bmax.0, y2));
+                self.draw_list.add_nine_slice(&image, rect, 0xffffffff);
+            }
             _ => {}
         }
 
@@ -432,7 +745,7 @@ impl Context {
             .stroke_gradient(border.thickness, false, border.color & 0x00ffffff, border.color);
     }
 
-    fn tab_item_text(&mut self, text: &str, bounds: Rect, style: TextStyle) {
+    pub(crate) fn tab_item_text(&mut self, text: &str, bounds: Rect, style: TextStyle) {
         let yoff = (bounds.height() - self.default_font.advance_y()) * 0.5f32;
         self.draw_list.add_text_wrapped(
             &mut *self.renderer,
@@ -0,0 +1,655 @@
+use crate::{
+    Context, MouseButton,
+    Background,
+    ButtonFlags,
+    CursorType,
+    Id,
+    PanelFlags,
+    PoolIndex,
+    RowLayoutType,
+    TextAlignment,
+
+    math::{
+        float2,
+        float4,
+        Rect,
+    }
+};
+
+use super::{
+    WidgetState,
+};
+
+const MIN_COLUMN_WIDTH: f32 = 16f32;
+const RESIZE_GRAB_SIZE: f32 = 6f32;
+const SORT_ARROW_SIZE: f32 = 4f32;
+
+bitflags! {
+    pub struct TableFlags: u32 {
+        const None = 0;
+        /// Columns can be resized by dragging the borders of the header row.
+        const Resizable = 1 << 0;
+        /// Clicking a header sorts by that column, see `table_sort_specs`.
+        const Sortable = 1 << 1;
+        /// Alternate the background of even and odd rows.
+        const RowBg = 1 << 2;
+        /// Vertical lines between the columns.
+        const BordersInnerV = 1 << 3;
+    }
+}
+
+bitflags! {
+    pub struct TableColumnFlags: u32 {
+        const None = 0;
+        const NoResize = 1 << 0;
+        const NoSort = 1 << 1;
+        /// Sort by this column until a header gets clicked.
+        const DefaultSort = 1 << 2;
+        /// Sort descending when the column gets sorted by first.
+        const PreferSortDescending = 1 << 3;
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct TableSortSpecs {
+    pub column: usize,
+    pub direction: SortDirection,
+    /// Set the first time the specs are read after they changed, sort the
+    /// data again when it is.
+    pub dirty: bool,
+}
+
+#[derive(Clone)]
+struct TableColumn {
+    name: String,
+    flags: TableColumnFlags,
+    /// Fixed width, unused while `stretch` is set.
+    width: f32,
+    stretch: bool,
+
+    min_x: f32,
+    max_x: f32,
+}
+
+impl TableColumn {
+    fn new() -> Self {
+        TableColumn {
+            name: String::new(),
+            flags: TableColumnFlags::None,
+            width: 0f32,
+            stretch: true,
+            min_x: 0f32,
+            max_x: 0f32,
+        }
+    }
+
+    /// `init_width` only applies the first time the column is seen, a
+    /// resized column keeps its width.
+    fn setup(&mut self, name: &str, flags: TableColumnFlags, init_width: f32) {
+        if self.name != name {
+            self.name = String::from(name);
+            self.width = init_width.max(MIN_COLUMN_WIDTH);
+            self.stretch = init_width <= 0f32;
+        }
+        self.flags = flags;
+    }
+
+    /// Grows the laid out column by `delta`, it has a fixed width after.
+    fn resize(&mut self, delta: f32) {
+        self.width = (self.max_x - self.min_x + delta).max(MIN_COLUMN_WIDTH);
+        self.stretch = false;
+    }
+}
+
+fn default_sort_direction(flags: TableColumnFlags) -> SortDirection {
+    if flags.contains(TableColumnFlags::PreferSortDescending) {
+        SortDirection::Descending
+    } else {
+        SortDirection::Ascending
+    }
+}
+
+#[derive(Clone)]
+pub struct Table {
+    id: Id,
+    flags: TableFlags,
+    columns: Vec<TableColumn>,
+    columns_count: usize,
+    setup_count: usize,
+    bounds: Rect,
+
+    laid_out: bool,
+    body_open: bool,
+    header_height: f32,
+    resize_column: Option<usize>,
+
+    current_row: isize,
+    current_column: isize,
+    cell_clipped: bool,
+    row_top: f32,
+    row_bottom: f32,
+    /// Row heights of the last frame, used to draw row backgrounds before
+    /// the cells are known.
+    row_heights: Vec<f32>,
+
+    sort: Option<(usize, SortDirection)>,
+    sort_dirty: bool,
+}
+
+impl Table {
+    fn new() -> Self {
+        Table {
+            id: 0,
+            flags: TableFlags::None,
+            columns: Vec::new(),
+            columns_count: 0,
+            setup_count: 0,
+            bounds: Rect::new(float2(0f32, 0f32), float2(0f32, 0f32)),
+
+            laid_out: false,
+            body_open: false,
+            header_height: 0f32,
+            resize_column: None,
+
+            current_row: -1,
+            current_column: -1,
+            cell_clipped: false,
+            row_top: 0f32,
+            row_bottom: 0f32,
+            row_heights: Vec::new(),
+
+            sort: None,
+            sort_dirty: false,
+        }
+    }
+
+    /// Sorts by `column`, clicking the sorted column again flips the
+    /// direction.
+    fn click_sort(&mut self, column: usize) {
+        let direction = match self.sort {
+            Some((c, SortDirection::Ascending)) if c == column => SortDirection::Descending,
+            Some((c, SortDirection::Descending)) if c == column => SortDirection::Ascending,
+            _ => default_sort_direction(self.columns[column].flags),
+        };
+        self.sort = Some((column, direction));
+        self.sort_dirty = true;
+    }
+
+    fn sort_specs(&mut self) -> Option<TableSortSpecs> {
+        let (column, direction) = self.sort?;
+        let dirty = self.sort_dirty;
+        self.sort_dirty = false;
+
+        Some(TableSortSpecs {
+            column,
+            direction,
+            dirty,
+        })
+    }
+
+    /// Places the columns inside the table bounds, stretch columns share
+    /// what's left after the fixed ones.
+    fn layout(&mut self) {
+        let (fixed, stretch_count) = self.columns.iter()
+            .fold((0f32, 0), |(fixed, count), col| {
+                if col.stretch {
+                    (fixed, count + 1)
+                } else {
+                    (fixed + col.width, count)
+                }
+            });
+
+        let stretch_width = if stretch_count > 0 {
+            ((self.bounds.width() - fixed) / stretch_count as f32).max(MIN_COLUMN_WIDTH)
+        } else {
+            0f32
+        };
+
+        let mut x = self.bounds.min.0;
+        for col in &mut self.columns {
+            let width = if col.stretch { stretch_width } else { col.width };
+
+            col.min_x = x;
+            col.max_x = x + width;
+            x = col.max_x;
+        }
+
+        self.laid_out = true;
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table::new()
+    }
+}
+
+impl Context {
+    /// Table filling the next widget slot, the rows below the header row
+    /// scroll. Set up the columns with `table_setup_column`, then submit
+    /// cells with `table_next_row`/`table_next_column` and finish with
+    /// `end_table` when it returned true.
+    pub fn begin_table(&mut self, id: &str, columns: usize, flags: TableFlags) -> bool {
+        let id = self.id(id);
+        let idx = self.tables.get(id);
+        let (bounds, _state) = self.widget(None);
+        if columns == 0 {
+            return false;
+        }
+
+        let table = &mut self.tables[idx];
+        table.id = id;
+        table.flags = flags;
+        table.columns.resize(columns, TableColumn::new());
+        table.columns_count = columns;
+        table.setup_count = 0;
+        table.bounds = bounds;
+        table.laid_out = false;
+        table.body_open = false;
+        table.header_height = 0f32;
+        table.current_row = -1;
+        table.current_column = -1;
+        table.cell_clipped = false;
+
+        self.current_table.push(idx);
+
+        true
+    }
+
+    pub fn end_table(&mut self) {
+        let idx = match self.current_table.last() {
+            Some(&idx) => idx,
+            None => return,
+        };
+
+        if self.tables[idx].body_open {
+            self.table_end_row(idx);
+            self.end_panel();
+        }
+
+        let table = &mut self.tables[idx];
+        if table.current_row >= 0 {
+            table.row_heights.truncate(table.current_row as usize + 1);
+        }
+
+        let table = &self.tables[idx];
+        if table.flags.contains(TableFlags::BordersInnerV) {
+            let border = self.style.table.border;
+            let bounds = table.bounds;
+
+            for col in table.columns.iter().take(table.columns_count.saturating_sub(1)) {
+                let x = col.max_x.round() - border.thickness * 0.5f32;
+                self.draw_list.add_rect_filled(
+                    float2(x, bounds.min.1),
+                    float2(x + border.thickness, bounds.max.1),
+                    0f32,
+                    border.color
+                );
+            }
+        }
+
+        self.current_table.pop();
+    }
+
+    /// Declares the next column. `init_width` is used the first time the
+    /// column is seen, a width of 0 makes the column share the space left
+    /// by the fixed width ones.
+    pub fn table_setup_column(&mut self, name: &str, flags: TableColumnFlags, init_width: f32) {
+        let idx = match self.current_table.last() {
+            Some(&idx) => idx,
+            None => return,
+        };
+
+        let table = &mut self.tables[idx];
+        let n = table.setup_count;
+        if n >= table.columns_count {
+            return;
+        }
+
+        table.columns[n].setup(name, flags, init_width);
+
+        if table.sort.is_none() && flags.contains(TableColumnFlags::DefaultSort) {
+            table.sort = Some((n, default_sort_direction(flags)));
+            table.sort_dirty = true;
+        }
+
+        table.setup_count += 1;
+        table.laid_out = false;
+    }
+
+    /// Header row with the column names, it stays at the top while the rows
+    /// scroll. Handles sorting and resizing.
+    pub fn table_headers_row(&mut self) {
+        let idx = match self.current_table.last() {
+            Some(&idx) => idx,
+            None => return,
+        };
+        if self.tables[idx].body_open {
+            return;
+        }
+
+        let style = &self.style.table;
+        let padding = style.cell_padding;
+        let height = self.default_font.height() + padding.1 * 2f32;
+
+        let table = &mut self.tables[idx];
+        table.layout();
+        table.header_height = height;
+
+        let flags = table.flags;
+        let top = table.bounds.min.1;
+        let count = table.columns_count;
+
+        for i in 0..count {
+            let (min_x, max_x, col_flags) = {
+                let col = &self.tables[idx].columns[i];
+                (col.min_x, col.max_x, col.flags)
+            };
+
+            let resizable = flags.contains(TableFlags::Resizable) && !col_flags.contains(TableColumnFlags::NoResize);
+            let sortable = flags.contains(TableFlags::Sortable) && !col_flags.contains(TableColumnFlags::NoSort);
+
+            let grab = Rect::new(
+                float2(max_x - RESIZE_GRAB_SIZE * 0.5f32, top),
+                float2(max_x + RESIZE_GRAB_SIZE * 0.5f32, top + height)
+            );
+            let header = Rect::new(float2(min_x, top), float2(max_x, top + height));
+            let button = if resizable {
+                Rect::new(header.min, float2(grab.min.0, header.max.1))
+            } else {
+                header
+            };
+
+            self.last_widget_state = WidgetState::None;
+            if sortable && self.button_behaviour(button, ButtonFlags::None) {
+                self.tables[idx].click_sort(i);
+            }
+
+            let style = &self.style.table;
+            let state = self.last_widget_state;
+            let background = if !sortable {
+                style.header
+            } else if state.contains(WidgetState::Active) {
+                style.header_active
+            } else if state.contains(WidgetState::Hovering) {
+                style.header_hover
+            } else {
+                style.header
+            };
+            let text = style.header_text;
+            let arrow_color = style.sort_arrow;
+
+            match background {
+                Background::Color(col) => {
+                    self.draw_list.add_rect_filled(header.min, header.max, 0f32, col);
+                },
+                Background::NineSlice(image) => {
+                    self.draw_list.add_nine_slice(&image, header, 0xffffffff);
+                },
+                _ => {}
+            }
+
+            let inner = header.pad_sides(padding.0, padding.1, padding.0, padding.1);
+            let sort = self.tables[idx].sort;
+            self.draw_list.push_clip_rect(header.to_float4());
+            self.draw_list.add_text_wrapped(
+                &mut *self.renderer,
+                &mut self.default_font,
+                &self.tables[idx].columns[i].name,
+                inner.min,
+                TextAlignment::Left,
+                inner.width(),
+                text.color
+            );
+
+            if let Some((column, direction)) = sort {
+                if column == i {
+                    let c = float2(inner.max.0 - SORT_ARROW_SIZE, inner.min.1 + inner.height() * 0.5f32);
+                    let s = SORT_ARROW_SIZE;
+                    let path = match direction {
+                        SortDirection::Ascending => self.draw_list.path()
+                            .line(float2(c.0 - s, c.1 + s * 0.5f32))
+                            .line(float2(c.0 + s, c.1 + s * 0.5f32))
+                            .line(float2(c.0, c.1 - s * 0.5f32)),
+                        SortDirection::Descending => self.draw_list.path()
+                            .line(float2(c.0 - s, c.1 - s * 0.5f32))
+                            .line(float2(c.0, c.1 + s * 0.5f32))
+                            .line(float2(c.0 + s, c.1 - s * 0.5f32)),
+                    };
+                    path.fill(arrow_color);
+                }
+            }
+            self.draw_list.pop_clip_rect();
+
+            if resizable {
+                self.table_resize_column(idx, i, grab);
+            }
+        }
+
+        self.tables[idx].layout();
+    }
+
+    /// Starts a new row, the first call also starts the table body.
+    pub fn table_next_row(&mut self) {
+        let idx = match self.current_table.last() {
+            Some(&idx) => idx,
+            None => return,
+        };
+
+        if self.tables[idx].body_open {
+            self.table_end_row(idx);
+        } else {
+            self.table_begin_body(idx);
+        }
+
+        let min_height = self.table_min_row_height();
+        let panel = &self.panel_stack[self.current_panel];
+        let top = panel.cursor.1;
+
+        let table = &mut self.tables[idx];
+        table.current_row += 1;
+        table.current_column = -1;
+        table.row_top = top;
+        table.row_bottom = top + min_height;
+
+        let row = table.current_row as usize;
+        if table.flags.contains(TableFlags::RowBg) {
+            let height = table.row_heights.get(row).cloned().unwrap_or(min_height);
+            let style = &self.style.table;
+            let color = if row % 2 == 0 { style.row_bg } else { style.row_bg_alt };
+            let bounds = table.bounds;
+
+            if top + height >= panel.clip.min.1 && top <= panel.clip.max.1 {
+                self.draw_list.add_rect_filled(
+                    float2(bounds.min.0, top),
+                    float2(bounds.max.0, top + height),
+                    0f32,
+                    color
+                );
+            }
+        }
+    }
+
+    /// Moves to the next cell, wrapping to a new row after the last column.
+    /// Widgets submitted afterwards are placed inside the cell. Returns
+    /// false when the row is scrolled out of view.
+    pub fn table_next_column(&mut self) -> bool {
+        let idx = match self.current_table.last() {
+            Some(&idx) => idx,
+            None => return false,
+        };
+
+        if self.tables[idx].current_row < 0 {
+            self.table_next_row();
+        } else {
+            self.table_end_cell(idx);
+        }
+
+        let table = &mut self.tables[idx];
+        table.current_column += 1;
+        if table.current_column as usize >= table.columns_count {
+            self.table_next_row();
+            self.tables[idx].current_column = 0;
+        }
+
+        let min_height = self.table_min_row_height();
+        let padding = self.style.table.cell_padding;
+        let window_padding = self.panel_stack[self.current_panel].get_padding(&self.style.window);
+        let scroll_x = self.panel_stack[self.current_panel].offset.0;
+
+        let table = &mut self.tables[idx];
+        let col = &table.columns[table.current_column as usize];
+        let (min_x, max_x) = (col.min_x - scroll_x, col.max_x - scroll_x);
+        let cell = Rect::new(
+            float2(min_x + padding.0, table.row_top + padding.1),
+            float2(max_x - padding.0, table.row_bottom - padding.1)
+        );
+        let clip = float4(min_x, table.bounds.min.1, max_x, table.bounds.max.1);
+        table.cell_clipped = true;
+
+        let panel = &mut self.panel_stack[self.current_panel];
+        panel.cursor = float2(cell.min.0 - window_padding.0, cell.min.1);
+        panel.row.ty = RowLayoutType::StaticFixed;
+        panel.row.index = 0;
+        panel.row.columns = 1;
+        panel.row.item_width = cell.width();
+        panel.row.item_offset = 0f32;
+        panel.row.max_height = 0f32;
+        panel.row.height = min_height - padding.1 * 2f32;
+        panel.row.default_height = panel.row.height;
+
+        let visible = cell.max.1 >= panel.clip.min.1 && cell.min.1 <= panel.clip.max.1;
+        self.draw_list.push_clip_rect(clip);
+
+        visible
+    }
+
+    /// Sorting requested through the headers, `None` until a sortable
+    /// header was clicked or a column has `DefaultSort`.
+    pub fn table_sort_specs(&mut self) -> Option<TableSortSpecs> {
+        let idx = *self.current_table.last()?;
+
+        self.tables[idx].sort_specs()
+    }
+
+    fn table_min_row_height(&self) -> f32 {
+        self.default_font.height() + self.style.table.cell_padding.1 * 2f32
+    }
+
+    fn table_resize_column(&mut self, idx: PoolIndex, column: usize, grab: Rect) {
+        let io = &mut self.io;
+        let hovered = io.has_mouse_in_rect(grab);
+        let table = &mut self.tables[idx];
+
+        if io.is_mouse_pressed(MouseButton::Left) && hovered {
+            table.resize_column = Some(column);
+        }
+        if !io.is_mouse_down(MouseButton::Left) && table.resize_column == Some(column) {
+            table.resize_column = None;
+        }
+
+        if table.resize_column == Some(column) {
+            table.columns[column].resize(io.mouse_delta.0);
+        }
+
+        if hovered || table.resize_column == Some(column) {
+            self.cursor = CursorType::ResizeHorizontal;
+        }
+    }
+
+    fn table_begin_body(&mut self, idx: PoolIndex) {
+        let table = &mut self.tables[idx];
+        if !table.laid_out {
+            table.layout();
+        }
+
+        let bounds = Rect::new(
+            float2(table.bounds.min.0, table.bounds.min.1 + table.header_height),
+            table.bounds.max
+        );
+        let title = format!("##table{}", table.id);
+        table.body_open = true;
+
+        self.begin_panel_ex(&title, Some(bounds), None, PanelFlags::None);
+    }
+
+    fn table_end_cell(&mut self, idx: PoolIndex) {
+        let padding = self.style.table.cell_padding;
+        let panel = &self.panel_stack[self.current_panel];
+        let bottom = panel.cursor.1 + panel.row.max_height + padding.1;
+
+        let table = &mut self.tables[idx];
+        table.row_bottom = table.row_bottom.max(bottom);
+
+        if table.cell_clipped {
+            table.cell_clipped = false;
+            self.draw_list.pop_clip_rect();
+        }
+    }
+
+    fn table_end_row(&mut self, idx: PoolIndex) {
+        if self.tables[idx].current_row < 0 {
+            return;
+        }
+        self.table_end_cell(idx);
+
+        let table = &mut self.tables[idx];
+        let row = table.current_row as usize;
+        if table.row_heights.len() <= row {
+            table.row_heights.resize(row + 1, 0f32);
+        }
+        table.row_heights[row] = table.row_bottom - table.row_top;
+
+        let bottom = table.row_bottom;
+        let panel = &mut self.panel_stack[self.current_panel];
+        panel.cursor.1 = bottom;
+        panel.row.index = 0;
+        panel.row.max_height = 0f32;
+        panel.max_y = panel.max_y.max(bottom);
+    }
+}
+
+#[test]
+fn table_sort_and_resize() {
+    let mut table = Table::new();
+    table.columns.resize(2, TableColumn::new());
+    table.columns_count = 2;
+    table.bounds = Rect::new(float2(0f32, 0f32), float2(300f32, 100f32));
+
+    table.columns[0].setup("Name", TableColumnFlags::None, 0f32);
+    table.columns[1].setup("Size", TableColumnFlags::PreferSortDescending, 80f32);
+    table.layout();
+    assert_eq!((table.columns[0].min_x, table.columns[0].max_x), (0f32, 220f32));
+    assert_eq!((table.columns[1].min_x, table.columns[1].max_x), (220f32, 300f32));
+
+    // dragging makes the width stick, setting up the column again doesn't
+    // go back to `init_width`
+    table.columns[1].resize(20f32);
+    table.columns[1].setup("Size", TableColumnFlags::PreferSortDescending, 80f32);
+    table.layout();
+    assert_eq!((table.columns[1].min_x, table.columns[1].max_x), (200f32, 300f32));
+
+    table.columns[0].resize(-300f32);
+    assert_eq!(table.columns[0].width, MIN_COLUMN_WIDTH);
+    assert!(!table.columns[0].stretch);
+
+    assert!(table.sort_specs().is_none());
+
+    table.click_sort(1);
+    let specs = table.sort_specs().unwrap();
+    assert_eq!((specs.column, specs.direction, specs.dirty), (1, SortDirection::Descending, true));
+    assert!(!table.sort_specs().unwrap().dirty);
+
+    table.click_sort(1);
+    assert_eq!(table.sort_specs().unwrap().direction, SortDirection::Ascending);
+
+    table.click_sort(0);
+    let specs = table.sort_specs().unwrap();
+    assert_eq!((specs.column, specs.direction), (0, SortDirection::Ascending));
+}
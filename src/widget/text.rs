@@ -14,6 +14,7 @@ use crate::{
 
 use super::{
     WidgetState,
+    WidgetLayoutState,
 };
 
 impl Context {
@@ -24,6 +25,13 @@ impl Context {
         h + style.padding.1 * 2f32
     }
     
+    fn paragraph_width(&mut self, text: &str) -> f32 {
+        let style = &self.style.paragraph;
+        let w = self.default_font.text_width(&mut *self.renderer, text);
+
+        w + style.padding.0 * 2f32
+    }
+
     pub fn paragraph_styled(&mut self, text: &str, bounds: Rect, padding: float2, style: TextStyle) {
         self.draw_list.add_text_wrapped(
             &mut *self.renderer,
@@ -38,11 +46,19 @@ impl Context {
     
     pub fn paragraph(&mut self, text: &str) {
         self.last_widget_state = WidgetState::None;
+        self.widget_content_width(|cxt| cxt.paragraph_width(text));
 
-        let w = self.peek_widget_width();
-        let h = self.paragraph_height(text, w);
+        let h = if self.widget_below_clip() {
+            self.default_font.height() + self.style.paragraph.padding.1 * 2f32
+        } else {
+            let w = self.peek_widget_width();
+            self.paragraph_height(text, w)
+        };
 
         let (bounds, state) = self.widget(Some(h));
+        if state == WidgetLayoutState::Hidden {
+            return;
+        }
 
         let style = &self.style.paragraph;
         self.paragraph_styled(text, bounds, style.padding, style.normal_text);
@@ -17,6 +17,7 @@ use crate::{
 
 use super::{
     WidgetState,
+    WidgetLayoutState,
 };
 
 impl Context {
@@ -46,7 +47,7 @@ impl Context {
         }
     }
 
-    fn draw_textfield_bg(&mut self, bounds: Rect, border: Border, bg: Background) {
+    pub(crate) fn draw_textfield_bg(&mut self, bounds: Rect, border: Border, bg: Background) {
         let bmin = bounds.min.round();
         let bmax = bounds.max.round();
         
@@ -58,6 +59,9 @@ impl Context {
                     c
                 );
             }
+            Background::NineSlice(image) => {
+                self.draw_list.add_nine_slice(&image, Rect::new(bmin, bmax), 0xffffffff);
+            }
             _ => {}
         }
 
@@ -96,7 +100,17 @@ impl Context {
 
         self.last_widget_state = WidgetState::None;
         let (bounds, state) = self.widget(Some(self.default_font.height() + pad));
+        // the field being edited keeps handling input while scrolled away
+        if state == WidgetLayoutState::Hidden && self.active_id != id {
+            return;
+        }
 
+        self.textfield_ex(id, bounds, text);
+    }
+
+    /// Text field over `bounds`, showing `text` until it's clicked and the
+    /// edit state takes over.
+    pub(crate) fn textfield_ex(&mut self, id: Id, bounds: Rect, text: &str) {
         let mut clear_active_id = false;
 
         let io = &self.io;
@@ -114,7 +128,7 @@ impl Context {
             if self.active_id != id {
                 self.text_edit_state.buffer.clear();
                 self.text_edit_state.buffer.extend(text.chars());
-                self.text_edit_state.backup = text.clone();
+                self.text_edit_state.backup = text.to_owned();
                 
                 self.text_edit_state.cursor = 0;
                 self.text_edit_state.clear_selection();
@@ -146,7 +160,7 @@ impl Context {
         let (text, offset) = if self.active_id == id {
             (None, self.text_edit_state.scroll_x)
         } else {
-            (Some(text.as_str()), 0f32)
+            (Some(text), 0f32)
         };
 
         let padding = self.style.textfield.padding.0;
@@ -170,6 +184,7 @@ impl Context {
         }
         
         self.draw_textfield_bg(bounds, border, background);
+        self.draw_list.push_clip_rect(clip_bounds.to_float4());
         self.draw_textfield_text(clip_bounds, text, offset, text_style);
 
         if self.active_id == id {
@@ -219,9 +234,9 @@ impl Context {
             if self.text_edit_state.is_cursor_visible() {
                 self.draw_list.add_rect_filled(cursor_rect.min, cursor_rect.max, 0f32, 0xffffffff);
             }
-
-
         }
+
+        self.draw_list.pop_clip_rect();
     }
 }
 
@@ -269,6 +284,11 @@ impl EditState {
         }
     }
 
+    /// Text being edited.
+    pub(crate) fn chars(&self) -> &[char] {
+        &self.buffer
+    }
+
     pub fn click(&mut self, cur: usize) {
         self.cursor = cur;
         self.selection_start = self.cursor;
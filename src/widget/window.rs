@@ -1,6 +1,8 @@
 use crate::{
     Context, MouseButton, Id, hash_id,
     WidgetState,
+    ButtonFlags,
+    UColor,
     Background,
     CursorType,
     PanelFlags,
@@ -18,12 +20,62 @@ use super::{
     Panel, PanelType,
 };
 
+/// When a `set_next_window_*` call should take effect.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Condition {
+    /// Every frame the call is made.
+    Always,
+    /// The first time the call is made for a window.
+    Once,
+    /// Only when the window is created.
+    FirstUseEver,
+}
+
+impl Condition {
+    fn allows(self, applied: &mut bool, is_new: bool) -> bool {
+        match self {
+            Condition::Always => true,
+            Condition::Once => {
+                let allowed = !*applied;
+                *applied = true;
+
+                allowed
+            },
+            Condition::FirstUseEver => is_new,
+        }
+    }
+}
+
+pub struct NextWindowData {
+    pos: Option<(float2, Condition)>,
+    size: Option<(float2, Condition)>,
+    size_constraints: Option<(float2, float2)>,
+    collapsed: Option<(bool, Condition)>,
+    focus: bool,
+}
+
+impl NextWindowData {
+    pub fn new() -> Self {
+        NextWindowData {
+            pos: None,
+            size: None,
+            size_constraints: None,
+            collapsed: None,
+            focus: false,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) enum WindowHeaderButton {
+    Close,
+    Collapse,
+    Expand,
+}
+
 struct WindowPerFrameData {
     cursor_start: float2,
     cursor: float2,
-    cursor_prev_line: float2,
-    cursor_max_pos: float2,
-    indent: f32,
     current_text_base_offset: f32,
 }
 
@@ -32,9 +84,6 @@ impl WindowPerFrameData {
         WindowPerFrameData {
             cursor_start: float2(0f32, 0f32),
             cursor: float2(0f32, 0f32),
-            cursor_prev_line: float2(0f32, 0f32),
-            cursor_max_pos: float2(0f32, 0f32),
-            indent: 0f32,
             current_text_base_offset: 0f32,
         }
     }
@@ -74,7 +123,25 @@ pub struct Window {
     pub bounds: Rect,
     pub flags: WindowFlags,
     pub scrollbar: float2,
+    pub min_size: float2,
+    pub max_size: float2,
+    /// Dock node the window is docked into, if any.
+    pub dock_node: Option<usize>,
+    /// Bounds to restore when the window gets undocked.
+    pub floating_bounds: Rect,
     data: WindowPerFrameData,
+
+    pos_set_once: bool,
+    size_set_once: bool,
+    /// Position and size set with `Once` or `FirstUseEver`, kept for windows
+    /// that get their bounds from `begin` every frame.
+    pos_override: Option<float2>,
+    size_override: Option<float2>,
+    collapsed_set_once: bool,
+    /// Created by `load_settings` without a position or size, `begin`
+    /// fills in the missing ones the first time it sees the window.
+    pub(crate) missing_pos: bool,
+    pub(crate) missing_size: bool,
 }
 
 impl Window {
@@ -87,7 +154,41 @@ impl Window {
             flags,
 
             scrollbar: float2(0f32, 0f32),
+            min_size: float2(0f32, 0f32),
+            max_size: float2(::std::f32::MAX, ::std::f32::MAX),
+            dock_node: None,
+            floating_bounds: bounds,
             data: WindowPerFrameData::new(),
+
+            pos_set_once: false,
+            size_set_once: false,
+            pos_override: None,
+            size_override: None,
+            collapsed_set_once: false,
+            missing_pos: false,
+            missing_size: false,
+        }
+    }
+
+    /// Clamps the window size to its constraints, keeping the edges that
+    /// did not move since `prev` in place.
+    pub fn constrain_size(&mut self, prev: Rect, min_size: float2) {
+        let min = float2(self.min_size.0.max(min_size.0), self.min_size.1.max(min_size.1));
+        let max = float2(self.max_size.0.max(min.0), self.max_size.1.max(min.1));
+
+        let w = self.bounds.width().max(min.0).min(max.0);
+        let h = self.bounds.height().max(min.1).min(max.1);
+
+        if self.bounds.min.0 != prev.min.0 && self.bounds.max.0 == prev.max.0 {
+            self.bounds.min.0 = self.bounds.max.0 - w;
+        } else {
+            self.bounds.max.0 = self.bounds.min.0 + w;
+        }
+
+        if self.bounds.min.1 != prev.min.1 && self.bounds.max.1 == prev.max.1 {
+            self.bounds.min.1 = self.bounds.max.1 - h;
+        } else {
+            self.bounds.max.1 = self.bounds.min.1 + h;
         }
     }
 
@@ -97,9 +198,36 @@ impl Window {
             && !self.flags.contains(WindowFlags::Hidden)
     }
 
+    /// Whether `bounding_box` is entirely outside the window.
     pub fn is_clipped(&self, bounding_box: Rect) -> bool {
-        true // self.clip_rect.outside(bounding_box)
+        self.bounds.clip(bounding_box).is_none()
+    }
+}
+
+/// Index of the window on top at `point` among `(stack position, visible
+/// bounds, flags)` entries, skipping background and closed windows.
+fn topmost_window_at<I: Iterator<Item = (usize, Rect, WindowFlags)>>(windows: I, point: float2) -> Option<usize> {
+    let mut top = None;
+    let mut p = 0;
+
+    for (i, (pos, bounds, flags)) in windows.enumerate() {
+        if flags.contains(WindowFlags::Background) || flags.contains(WindowFlags::Closed) {
+            continue;
+        }
+
+        if bounds.contains(point) && pos >= p {
+            top = Some(i);
+            p = pos;
+        }
     }
+
+    top
+}
+
+/// A double-click on the header only collapses the focused window, the
+/// ones behind it are read only after the click.
+fn header_toggles_collapse(flags: WindowFlags, double_clicked: bool) -> bool {
+    double_clicked && !flags.contains(WindowFlags::ReadOnly)
 }
 
 impl Context {
@@ -125,13 +253,13 @@ impl Context {
         self.window_stack.push(wnd);
     }
     
-    fn move_window_to_front(&mut self, idx: usize) {
+    pub(crate) fn move_window_to_front(&mut self, idx: usize) {
         if let Some(wnd) = self.get_window_stack_pos(idx) {
             self.move_stack_pos_to_front(wnd);
         }
     }
     
-    fn create_window(&mut self, name: &str, hash: Id, bounds: Rect, flags: WindowFlags) -> usize {
+    pub(crate) fn create_window(&mut self, name: &str, hash: Id, bounds: Rect, flags: WindowFlags) -> usize {
         let mut wnd = Window::new(name.into(), hash, bounds, flags);
 
         let idx = self.windows.len();
@@ -141,7 +269,7 @@ impl Context {
         idx
     }
 
-    fn find_window_hash(&self, hash: Id) -> Option<usize> {
+    pub(crate) fn find_window_hash(&self, hash: Id) -> Option<usize> {
         let len = self.windows.len();
         let mut i = 0;
         
@@ -168,6 +296,19 @@ impl Context {
         self.begin_titled(title, Rect::new(float2(10f32, 10f32) + offset, float2(410f32, 510f32) + offset), flags)
     }
 
+    /// Like `begin`, but adds a close button to the header. `open` is set
+    /// to false when the window gets closed, and the window is shown again
+    /// once the caller sets it back to true.
+    pub fn begin_open(&mut self, title: &str, open: &mut bool, flags: WindowFlags) -> bool {
+        let offset = float2(40f32, 40f32) * self.window_stack.len() as f32;
+        self.begin_window(
+            title,
+            Rect::new(float2(10f32, 10f32) + offset, float2(410f32, 510f32) + offset),
+            flags | WindowFlags::Closable,
+            Some(open)
+        )
+    }
+
     pub fn begin_root(&mut self, title: &str, flags: WindowFlags) -> bool {
         self.begin_titled(
             title,
@@ -177,70 +318,187 @@ impl Context {
     }
     
     pub fn begin_titled(&mut self, title: &str, bounds: Rect, flags: WindowFlags) -> bool {
+        self.begin_window(title, bounds, flags, None)
+    }
+
+    pub fn set_next_window_pos(&mut self, pos: float2, cond: Condition) {
+        self.next_window.pos = Some((pos, cond));
+    }
+
+    pub fn set_next_window_size(&mut self, size: float2, cond: Condition) {
+        self.next_window.size = Some((size, cond));
+    }
+
+    pub fn set_next_window_size_constraints(&mut self, min: float2, max: float2) {
+        self.next_window.size_constraints = Some((min, max));
+    }
+
+    pub fn set_next_window_collapsed(&mut self, collapsed: bool, cond: Condition) {
+        self.next_window.collapsed = Some((collapsed, cond));
+    }
+
+    pub fn set_next_window_focus(&mut self) {
+        self.next_window.focus = true;
+    }
+
+    fn apply_next_window_data(&mut self, idx: usize, is_new: bool) {
+        let next = ::std::mem::replace(&mut self.next_window, NextWindowData::new());
+        let min_size = self.style.window.min_size;
+        let wnd = &mut self.windows[idx];
+
+        if let Some((min, max)) = next.size_constraints {
+            wnd.min_size = min;
+            wnd.max_size = max;
+        }
+
+        if let Some((pos, cond)) = next.pos {
+            if cond.allows(&mut wnd.pos_set_once, is_new) {
+                let size = wnd.bounds.max - wnd.bounds.min;
+                wnd.bounds = Rect::new(pos, pos + size);
+                if cond != Condition::Always {
+                    wnd.pos_override = Some(pos);
+                }
+            }
+        }
+
+        if let Some((size, cond)) = next.size {
+            if cond.allows(&mut wnd.size_set_once, is_new) {
+                wnd.bounds.max = wnd.bounds.min + size;
+                if cond != Condition::Always {
+                    wnd.size_override = Some(size);
+                }
+            }
+        }
+
+        if let Some((collapsed, cond)) = next.collapsed {
+            if cond.allows(&mut wnd.collapsed_set_once, is_new) {
+                wnd.flags.set(WindowFlags::Minimized, collapsed);
+            }
+        }
+
+        if !wnd.flags.contains(WindowFlags::Background) {
+            let bounds = wnd.bounds;
+            wnd.constrain_size(bounds, min_size);
+        }
+
+        if next.focus {
+            let mut i = 0;
+            let len = self.windows.len();
+            while i < len {
+                if i != idx {
+                    self.windows[i].flags.insert(WindowFlags::ReadOnly);
+                }
+
+                i += 1;
+            }
+
+            self.windows[idx].flags.remove(WindowFlags::ReadOnly);
+            self.move_window_to_front(idx);
+        }
+    }
+
+    fn window_header_height(&self) -> f32 {
+        let style = &self.style.window.header;
+
+        14f32 + 2f32 * style.padding.1 + 2f32 * style.label_padding.1
+    }
+
+    fn window_visible_bounds(&self, idx: usize) -> Rect {
+        let wnd = &self.windows[idx];
+        let mut bounds = wnd.bounds;
+
+        if wnd.flags.contains(WindowFlags::Minimized) && wnd.has_header() {
+            bounds.max.1 = bounds.min.1 + self.window_header_height();
+        }
+
+        bounds
+    }
+    
+    fn begin_window(&mut self, title: &str, bounds: Rect, flags: WindowFlags, open: Option<&mut bool>) -> bool {
         let hash = hash_id(title);
         
-        let idx = if let Some(idx) = self.find_window_hash(hash) {
+        let (is_new, idx) = if let Some(idx) = self.find_window_hash(hash) {
             let wnd = &mut self.windows[idx];
             wnd.flags |= flags;
+            if wnd.missing_pos || wnd.missing_size {
+                let pos = if wnd.missing_pos { bounds.min } else { wnd.bounds.min };
+                let size = if wnd.missing_size { bounds.max - bounds.min } else { wnd.bounds.max - wnd.bounds.min };
+                wnd.bounds = Rect::new(pos, pos + size);
+                wnd.floating_bounds = wnd.bounds;
+                wnd.missing_pos = false;
+                wnd.missing_size = false;
+            }
             if !(wnd.flags.contains(WindowFlags::Movable) || wnd.flags.contains(WindowFlags::Scalable)) {
+                let mut bounds = bounds;
+                if let Some(pos) = wnd.pos_override {
+                    bounds = Rect::new(pos, pos + (bounds.max - bounds.min));
+                }
+                if let Some(size) = wnd.size_override {
+                    bounds.max = bounds.min + size;
+                }
                 wnd.bounds = bounds;
             }
 
-            idx
+            (false, idx)
         } else {
             let idx = self.create_window(title, hash, bounds, flags | WindowFlags::ReadOnly);
 
             self.window_stack.push(idx);
             
-            idx
+            (true, idx)
         };
 
+        self.apply_next_window_data(idx, is_new);
+
         let stack_pos = self.get_window_stack_pos(idx).unwrap();
         let wnd = &mut self.windows[idx];
-        let io = &self.io;
 
         if wnd.flags.contains(WindowFlags::Background) {
             wnd.bounds = Rect::new(float2(0f32, 0f32), self.io.display_size);
         }
 
-        if wnd.flags.contains(WindowFlags::Hidden) {
+        if let Some(open) = &open {
+            wnd.flags.set(WindowFlags::Closed, !**open);
+        }
+
+        if wnd.flags.contains(WindowFlags::Hidden) || wnd.flags.contains(WindowFlags::Closed) {
             return false;
         }
 
+        // docked windows only show while they're the selected tab of a node
+        // laid out by its dock space
+        if let Some(node) = wnd.dock_node {
+            let dock = &self.dock_nodes[node];
+            if !dock.alive || !dock.windows.contains(&hash) {
+                self.windows[idx].dock_node = None;
+            } else if dock.selected != hash || !dock.is_laid_out(self.frame) {
+                return false;
+            } else {
+                let bounds = self.dock_content_bounds(node);
+                self.windows[idx].bounds = bounds;
+            }
+        }
+        let wnd = &self.windows[idx];
+
         if !wnd.flags.contains(WindowFlags::NoInput) {
-            let bounds = wnd.bounds;
+            // borrowck {
+            drop(wnd);
+            let visible_bounds = self.window_visible_bounds(idx);
+            let io = &self.io;
+            let wnd = &self.windows[idx];
 
             let bg = wnd.flags.contains(WindowFlags::Background);
             let mouse_click = io.is_mouse_pressed(MouseButton::Left);
-            let mouse_down = io.is_mouse_down(MouseButton::Left);
-            let mouse_inside = mouse_click && io.has_mouse_click_in_rect(MouseButton::Left, wnd.bounds);
-            let mouse_hover = io.has_mouse_in_rect(wnd.bounds);
-
-            // borrowck {
-            drop(wnd);
+            let mouse_inside = mouse_click && io.has_mouse_click_in_rect(MouseButton::Left, visible_bounds);
             
-            let mut window_clicked = None;
-            if mouse_inside {
-                let len = self.window_stack.len();
-                let mut i = 0;
-                let mut p = 0;
-
-                while i < len {
-                    // if i == idx { continue; }
-                    let pos = self.window_stack[i];
-                    let wnd = &self.windows[i];
-                    let bounds = wnd.bounds;
-
-                    if !wnd.flags.contains(WindowFlags::Background) {
-                        if bounds.contains(io.mouse) && pos >= p {
-                            window_clicked = Some(i);
-                            p = pos;
-                        }
-                    }
+            let window_clicked = if mouse_inside {
+                let windows = (0..self.window_stack.len())
+                    .map(|i| (self.window_stack[i], self.window_visible_bounds(i), self.windows[i].flags));
 
-                    i += 1;
-                }
-            }
+                topmost_window_at(windows, io.mouse)
+            } else {
+                None
+            };
 
             let wnd = &mut self.windows[idx];
             // borrowck }
@@ -250,12 +508,14 @@ impl Context {
                 self.windows[wnd_idx].flags.remove(WindowFlags::ReadOnly);
                 //self.active = Some(wnd_idx);
                 
-                self.move_window_to_front(wnd_idx);
+                if self.windows[wnd_idx].dock_node.is_none() {
+                    self.move_window_to_front(wnd_idx);
+                }
             } else if mouse_inside {
                 wnd.flags.remove(WindowFlags::ReadOnly);
                 //self.active = Some(idx);
 
-                if !bg {
+                if !bg && wnd.dock_node.is_none() {
                     self.move_stack_pos_to_front(stack_pos);
                 }
             } else if mouse_click {
@@ -263,7 +523,11 @@ impl Context {
             }
         }
         self.active = Some(idx);
-        let wnd = &mut self.windows[idx];
+        let wnd = &self.windows[idx];
+        let docked = wnd.dock_node.is_some();
+        let wnd_flags = if docked { wnd.flags - WindowFlags::Minimized } else { wnd.flags };
+        let wnd_bounds = wnd.bounds;
+        let has_header = wnd.has_header() && !docked;
         /*wnd.layout.offset = wnd.scrollbar;
         let panel_padding = wnd.layout.get_padding(&self.style.window);
         wnd.layout.bounds = wnd.bounds.grow(
@@ -286,14 +550,15 @@ impl Context {
             (window.normal_border, window.normal, header.normal, header.normal_text)
         };
 
-        let mut body = wnd.bounds;
+        let mut body = wnd_bounds;
+        self.draw_list.push_clip_rect(wnd_bounds.to_float4());
+
+        let mut close = false;
+        let mut toggle = false;
         
-        if wnd.has_header() {
-            let style = &self.style.window.header;
-            let mut header = wnd.bounds;
-            header.max.1 = header.min.1;
-            header.max.1 += 14f32 + 2f32 * style.padding.1;
-            header.max.1 += 2f32 * style.label_padding.1;
+        if has_header {
+            let mut header = wnd_bounds;
+            header.max.1 = header.min.1 + self.window_header_height();
 
             let h = header.height();
             body.min.1 += h;
@@ -305,46 +570,175 @@ impl Context {
                 _ => {}
             }
 
-            if wnd.flags.contains(WindowFlags::Title) {
+            let style = &self.style.window.header;
+            let padding = style.padding;
+            let spacing = style.spacing.0.max(2f32);
+            let button_size = h - 2f32 * padding.1;
+            let minimized = wnd_flags.contains(WindowFlags::Minimized);
+
+            let mut label = header;
+            if wnd_flags.contains(WindowFlags::Closable) {
+                let min = float2(header.max.0 - padding.0 - button_size, header.min.1 + padding.1);
+                let button = Rect::new(min, min + float2(button_size, button_size));
+                label.max.0 = button.min.0 - spacing;
+
+                close = self.window_header_button(button, WindowHeaderButton::Close, text.color);
+            }
+
+            if wnd_flags.contains(WindowFlags::Minimizable) {
+                let min = float2(header.min.0 + padding.0, header.min.1 + padding.1);
+                let button = Rect::new(min, min + float2(button_size, button_size));
+                label.min.0 = button.max.0 + spacing;
+
+                let symbol = if minimized {
+                    WindowHeaderButton::Expand
+                } else {
+                    WindowHeaderButton::Collapse
+                };
+                toggle = self.window_header_button(button, symbol, text.color);
+            }
+
+            // double-clicking the title collapses any window with a header,
+            // not only the ones showing a collapse button
+            let io = &self.io;
+            let double_clicked = io.is_mouse_double_clicked(MouseButton::Left) && io.has_mouse_in_rect(label);
+            if header_toggles_collapse(wnd_flags, double_clicked) {
+                toggle = true;
+            }
+
+            if wnd_flags.contains(WindowFlags::Title) {
                 self.draw_list.add_text_wrapped(
                     &mut *self.renderer,
                     &mut self.default_font,
                     title,
-                    header.min,
+                    float2(label.min.0, header.min.1),
                     text.align,
-                    header.width(),
+                    label.width(),
                     text.color
                 );
             }
         }
 
-        if !wnd.flags.contains(WindowFlags::NoBg) {
+        let wnd = &mut self.windows[idx];
+        if close {
+            wnd.flags.insert(WindowFlags::Closed);
+            if let Some(open) = open {
+                *open = false;
+            }
+        }
+
+        if toggle {
+            wnd.flags.toggle(WindowFlags::Minimized);
+        }
+
+        if close || toggle {
+            self.mark_settings_dirty();
+        }
+
+        let wnd = &self.windows[idx];
+
+        if wnd.flags.contains(WindowFlags::Closed) || (wnd.flags.contains(WindowFlags::Minimized) && !docked) {
+            self.end_window();
+
+            return false;
+        }
+
+        if !wnd_flags.contains(WindowFlags::NoBg) {
             match background {
                 Background::Color(col) => {
                     self.draw_list.add_rect_filled(body.min, body.max, 0f32, col);
                 },
+                Background::NineSlice(image) => {
+                    self.draw_list.add_nine_slice(&image, body, 0xffffffff);
+                },
                 _ => {}
             }
 
             if border.thickness != 0f32 {
-                let border_bounds = wnd.bounds.pad(-border.thickness * 0.5f32);
+                let border_bounds = wnd_bounds.pad(-border.thickness * 0.5f32);
                 self.draw_list.add_rect(border_bounds.min, border_bounds.max, border.rounding, border.thickness, border.color);
             }
         }
 
-        self.begin_panel_ex(title, Some(body), None, PanelFlags::None)
+        let mut panel_flags = PanelFlags::None;
+        if wnd_flags.contains(WindowFlags::NoScrollbar) {
+            panel_flags |= PanelFlags::NoScrollbar;
+        }
+        if wnd_flags.contains(WindowFlags::ScrollAutoHide) {
+            panel_flags |= PanelFlags::ScrollAutoHide;
+        }
+
+        self.panel_push(title, body, None, panel_flags, false)
+    }
+
+    pub(crate) fn window_header_button(&mut self, bounds: Rect, symbol: WindowHeaderButton, color: UColor) -> bool {
+        self.last_widget_state = WidgetState::None;
+        let pressed = self.button_behaviour(bounds, ButtonFlags::None);
+
+        let style = &self.style.button;
+        let state = self.last_widget_state;
+        let background = if state.contains(WidgetState::Active) {
+            Some(style.active)
+        } else if state.contains(WidgetState::Hovering) {
+            Some(style.hover)
+        } else {
+            None
+        };
+
+        if let Some(Background::Color(col)) = background {
+            self.draw_list.add_rect_filled(bounds.min, bounds.max, 0f32, col);
+        }
+
+        let inner = bounds.pad((bounds.width() * 0.25f32).floor());
+        match symbol {
+            WindowHeaderButton::Close => {
+                self.draw_list.path()
+                    .line(inner.min)
+                    .line(inner.max)
+                    .stroke(1.5f32, false, color);
+                self.draw_list.path()
+                    .line(float2(inner.max.0, inner.min.1))
+                    .line(float2(inner.min.0, inner.max.1))
+                    .stroke(1.5f32, false, color);
+            }
+            WindowHeaderButton::Collapse => {
+                self.draw_list.path()
+                    .line(inner.min)
+                    .line(float2(inner.max.0, inner.min.1))
+                    .line(float2(inner.center().0, inner.max.1))
+                    .fill(color);
+            }
+            WindowHeaderButton::Expand => {
+                self.draw_list.path()
+                    .line(inner.min)
+                    .line(float2(inner.max.0, inner.center().1))
+                    .line(float2(inner.min.0, inner.max.1))
+                    .fill(color);
+            }
+        }
+
+        pressed
     }
 
     pub fn end(&mut self) {
         self.end_panel();
+        self.end_window();
+    }
+
+    fn end_window(&mut self) {
+        self.draw_list.pop_clip_rect();
 
         if let Some(idx) = self.active {
             self.draw_list.push_layer(idx as u32);
 
+            let scroll_idx = self.scroll_states.get(self.windows[idx].id);
+            self.windows[idx].scrollbar = self.scroll_states[scroll_idx].offset;
+
             let io = &mut self.io;
             let wnd = &mut self.windows[idx];
             // drag, resize handling
-            if wnd.flags.contains(WindowFlags::Movable) && !wnd.flags.contains(WindowFlags::ReadOnly) {
+            if wnd.flags.contains(WindowFlags::Movable) && !wnd.flags.contains(WindowFlags::ReadOnly)
+                && wnd.dock_node.is_none() {
                 let border_size = 3f32;
                 let handle_size = 5f32;
                 let handle_area = float2(handle_size, handle_size);
@@ -357,8 +751,9 @@ impl Context {
                 let mut header = wnd.bounds;
                 header.max.1 = header.min.1;
 
-                let style = &self.style.window.header;
+                let minimized = wnd.flags.contains(WindowFlags::Minimized);
                 if wnd.has_header() {
+                    let style = &self.style.window.header;
                     header.max.1 += 14f32 + 2f32 * style.padding.1;
                     header.max.1 += 2f32 * style.label_padding.1;
                 } else {
@@ -405,7 +800,7 @@ impl Context {
 
                 let in_header = io.has_mouse_in_rect(header);
                 
-                if !io.has_mouse_in_rect(header) {
+                if !io.has_mouse_in_rect(header) && !minimized {
                     if io.has_mouse_in_rect(left) {
                         self.cursor = CursorType::ResizeHorizontal;
                     } else if io.has_mouse_in_rect(right) {
@@ -425,11 +820,16 @@ impl Context {
                     }
                 }
 
+                let prev_bounds = wnd.bounds;
+                let mut dragging = false;
                 if mouse_down {
                     if io.has_mouse_click_in_rect(MouseButton::Left, header) {
+                        dragging = true;
                         wnd.bounds.min += io.mouse_delta;
                         wnd.bounds.max += io.mouse_delta;
                         io.mouse_clicked_pos[MouseButton::Left as usize] += io.mouse_delta;
+                    } else if minimized {
+                        // only the header can be dragged while minimized
                     } else if io.has_mouse_click_in_rect(MouseButton::Left, right) {
                         wnd.bounds.max.0 += io.mouse_delta.0;
                         io.mouse_clicked_pos[MouseButton::Left as usize].0 += io.mouse_delta.0;
@@ -459,6 +859,15 @@ impl Context {
                     }
                 }
 
+                // keep the drag anchored to the edge when hitting a size limit
+                let unclamped = wnd.bounds;
+                wnd.constrain_size(prev_bounds, self.style.window.min_size);
+                let correction = (wnd.bounds.min - unclamped.min) + (wnd.bounds.max - unclamped.max);
+                io.mouse_clicked_pos[MouseButton::Left as usize] += correction;
+
+                let moved = wnd.bounds.min.0 != prev_bounds.min.0 || wnd.bounds.min.1 != prev_bounds.min.1
+                    || wnd.bounds.max.0 != prev_bounds.max.0 || wnd.bounds.max.1 != prev_bounds.max.1;
+
                 if io.is_mouse_down(MouseButton::Right) {
                     self.draw_list.add_rect(left.min, left.max, 0f32, 1f32, 0x8800ffff);
                     self.draw_list.add_rect(right.min, right.max, 0f32, 1f32, 0x8800ffff);
@@ -474,9 +883,33 @@ impl Context {
 
                     self.draw_list.add_rect(header.min, header.max, 0f32, 1f32, 0x88ff00ff);
                 }
+
+                if moved {
+                    self.mark_settings_dirty();
+                }
+
+                self.dock_window_drag(idx, dragging);
             }
             
             self.active = None;
         }
     }
 }
+
+#[test]
+fn header_double_click_overlapping() {
+    let back = Rect::new(float2(0f32, 0f32), float2(200f32, 100f32));
+    let front = Rect::new(float2(50f32, 0f32), float2(250f32, 100f32));
+    let mouse = float2(100f32, 10f32);
+
+    let windows = vec![(1, back, WindowFlags::Title), (2, front, WindowFlags::Title)];
+    let clicked = topmost_window_at(windows.into_iter(), mouse);
+    assert_eq!(clicked, Some(1));
+
+    // the focus pass makes every other window read only
+    let flags: Vec<_> = (0..2)
+        .map(|i| if Some(i) == clicked { WindowFlags::Title } else { WindowFlags::Title | WindowFlags::ReadOnly })
+        .collect();
+    assert!(!header_toggles_collapse(flags[0], true));
+    assert!(header_toggles_collapse(flags[1], true));
+}